@@ -0,0 +1,471 @@
+// ─── epub2braille: conversión por lotes desde la línea de órdenes ────────────
+// Llama directamente al motor braille, sin Tauri ni ventana: pensado para servidores
+// de compilación y scripts que convierten catálogos enteros. Los mensajes para
+// personas van a stderr; stdout lleva solo el resumen en JSON.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use braille::{
+    self, Conversion, Enfasis, ErrorBraille, Excepciones, FormatoSalida, GuionBlando, Informe, Maquetacion,
+    OpcionesCodificacion, PuntoEnNumero, Sustitucion, TablaBraille,
+};
+use serde::Serialize;
+
+const AYUDA: &str = "\
+Uso: epub2braille [opciones] <entrada>...
+
+Cada <entrada> es un archivo .epub o un directorio, que se recorre
+recursivamente en busca de archivos .epub.
+
+Opciones:
+  -f, --formato <bin|brf|pef|txt>  Formato de salida (por defecto: bin)
+  -w, --ancho <celdas>             Celdas por línea (por defecto: 30)
+  -t, --tabla <nombre|archivo>     Tabla braille: una integrada (es, es-419, pt,
+                                   ca, gl, eu), una tabla liblouis incluida
+                                   (es-cbe.ctb) o la ruta de un archivo
+                                   .ctb/.utb de liblouis
+                                   (por defecto: la del idioma de cada libro)
+  -d, --desconocidos <modo>        Caracteres sin celda en la tabla: omitir,
+                                   marcador (celda 123456) o transliterar
+                                   (por defecto: omitir); siempre se listan
+                                   en el informe de cada libro
+      --sin-normalizar             Codifica el texto tal como viene, sin componer
+                                   acentos ni plegar ligaduras o espacios
+                                   especiales (los guiones blandos se siguen
+                                   quitando, salvo con --guion-blando)
+      --guion-blando               Parte las palabras que traen guiones blandos
+                                   solo por ellos, en lugar de silabearlas
+      --millares                   El punto entre cifras es separador de millares
+                                   (1.809 en 6 celdas, como el ejemplo de la CBE)
+                                   en lugar de repetir el prefijo numérico
+  -e, --excepciones <archivo>      Excepciones de silabeo: una palabra por línea
+                                   con sus sílabas separadas por guiones
+                                   (su-bli-me), o \"prefijo sub-r\" para cortar
+                                   tras un prefijo; se consultan antes que las
+                                   reglas de la tabla
+  -r, --resalte <clases>           Resaltes del libro que llevan indicador,
+                                   separados por comas: cursiva, negrita,
+                                   subrayado, o ninguno (por defecto: todos);
+                                   se reconocen por la etiqueta, el atributo
+                                   style o una clase de selector simple
+                                   (.italic, span.negrita) de sus hojas de estilo
+      --fragmento-minimo <letras>  Letras que deben quedar a cada lado de una
+                                   palabra partida a final de línea
+                                   (por defecto: 2, nunca a-ho-ra ni rí-o)
+      --partir-hiatos              Permite partir vocales en hiato (le-er)
+      --partir-nombres             Permite partir nombres propios y siglas
+  -o, --salida <directorio>        Dónde escribir los resultados (por defecto:
+                                   junto a cada .epub)
+  -h, --help                       Muestra esta ayuda
+
+Códigos de salida: 0 todo convertido, 1 algún libro falló, 2 uso incorrecto.";
+
+/// Códigos de salida.
+const SALIDA_FALLOS: u8 = 1;
+const SALIDA_USO: u8 = 2;
+
+struct Opciones {
+    entradas: Vec<PathBuf>,
+    formato: FormatoSalida,
+    maquetacion: Maquetacion,
+    codificacion: OpcionesCodificacion,
+    /// `None`: la del idioma de cada libro.
+    tabla: Option<Box<dyn TablaBraille>>,
+    salida: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Resultado {
+    entrada: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conversion: Option<Conversion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBraille>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Resumen {
+    convertidos: usize,
+    fallidos: usize,
+    resultados: Vec<Resultado>,
+}
+
+fn main() -> ExitCode {
+    let opciones = match leer_argumentos(std::env::args().skip(1)) {
+        Ok(Some(opciones)) => opciones,
+        Ok(None) => {
+            println!("{}", AYUDA);
+            return ExitCode::SUCCESS;
+        }
+        Err(mensaje) => {
+            eprintln!("epub2braille: {}\n\n{}", mensaje, AYUDA);
+            return ExitCode::from(SALIDA_USO);
+        }
+    };
+
+    let mut libros = Vec::new();
+    for entrada in &opciones.entradas {
+        if let Err(mensaje) = buscar_epubs(entrada, &mut libros) {
+            eprintln!("epub2braille: {}", mensaje);
+            return ExitCode::from(SALIDA_USO);
+        }
+    }
+    if libros.is_empty() {
+        eprintln!("epub2braille: no se encontró ningún .epub");
+        return ExitCode::from(SALIDA_USO);
+    }
+    let libros = match destinos(&libros, &opciones) {
+        Ok(libros) => libros,
+        Err(mensaje) => {
+            eprintln!("epub2braille: {}", mensaje);
+            return ExitCode::from(SALIDA_USO);
+        }
+    };
+
+    let resultados: Vec<Resultado> = libros.iter()
+        .map(|(epub, destino)| convertir(epub, destino, &opciones))
+        .collect();
+    let fallidos = resultados.iter().filter(|r| r.error.is_some()).count();
+    let resumen = Resumen {
+        convertidos: resultados.len() - fallidos,
+        fallidos,
+        resultados,
+    };
+    println!("{}", serde_json::to_string_pretty(&resumen).unwrap_or_default());
+
+    if fallidos > 0 {
+        ExitCode::from(SALIDA_FALLOS)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `Ok(None)` si se pidió la ayuda.
+fn leer_argumentos(mut args: impl Iterator<Item = String>) -> Result<Option<Opciones>, String> {
+    let mut opciones = Opciones {
+        entradas: Vec::new(),
+        formato: FormatoSalida::default(),
+        maquetacion: Maquetacion::default(),
+        codificacion: OpcionesCodificacion::default(),
+        tabla: None,
+        salida: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut valor = |nombre: &str| {
+            args.next().ok_or_else(|| format!("falta el valor de {}", nombre))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--formato" => {
+                opciones.formato = match valor(&arg)?.as_str() {
+                    "bin" => FormatoSalida::Bin,
+                    "brf" => FormatoSalida::Brf,
+                    "pef" => FormatoSalida::Pef,
+                    "txt" => FormatoSalida::Txt,
+                    otro => return Err(format!("formato desconocido: {}", otro)),
+                };
+            }
+            "-w" | "--ancho" => {
+                let ancho = valor(&arg)?;
+                opciones.maquetacion.celdas_por_linea = ancho.parse()
+                    .map_err(|_| format!("ancho no válido: {}", ancho))?;
+            }
+            "-t" | "--tabla" => {
+                opciones.tabla = Some(braille::elegir_tabla(&valor(&arg)?)
+                    .map_err(|e| e.to_string())?);
+            }
+            "-d" | "--desconocidos" => {
+                opciones.codificacion.desconocidos = match valor(&arg)?.as_str() {
+                    "omitir" => Sustitucion::Omitir,
+                    "marcador" => Sustitucion::Marcador,
+                    "transliterar" => Sustitucion::Transliterar,
+                    otro => return Err(format!("modo desconocido: {}", otro)),
+                };
+            }
+            "--sin-normalizar" => {
+                let normalizacion = &mut opciones.codificacion.normalizacion;
+                normalizacion.componer = false;
+                normalizacion.ligaduras = false;
+                normalizacion.espacios = false;
+            }
+            "--guion-blando" => {
+                opciones.codificacion.normalizacion.guion_blando = GuionBlando::PuntoDeCorte;
+            }
+            "--millares" => opciones.codificacion.punto_en_numero = PuntoEnNumero::Millares,
+            "-e" | "--excepciones" => {
+                let ruta = PathBuf::from(valor(&arg)?);
+                let excepciones = Excepciones::desde_archivo(&ruta).map_err(|e| e.to_string())?;
+                let propias = &mut opciones.codificacion.excepciones;
+                propias.palabras.extend(excepciones.palabras);
+                propias.prefijos.extend(excepciones.prefijos);
+            }
+            "-r" | "--resalte" => {
+                let mut enfasis = Enfasis::ninguno();
+                for clase in valor(&arg)?.split(',').map(str::trim) {
+                    match clase {
+                        "cursiva" => enfasis.cursiva = true,
+                        "negrita" => enfasis.negrita = true,
+                        "subrayado" => enfasis.subrayado = true,
+                        "ninguno" => {}
+                        otra => return Err(format!("resalte desconocido: {}", otra)),
+                    }
+                }
+                opciones.codificacion.enfasis = enfasis;
+            }
+            "--fragmento-minimo" => {
+                let letras = valor(&arg)?;
+                opciones.codificacion.division.fragmento_minimo = letras.parse()
+                    .map_err(|_| format!("fragmento mínimo no válido: {}", letras))?;
+            }
+            "--partir-hiatos" => opciones.codificacion.division.hiatos = true,
+            "--partir-nombres" => opciones.codificacion.division.nombres_propios = true,
+            "-o" | "--salida" => opciones.salida = Some(PathBuf::from(valor(&arg)?)),
+            _ if arg.starts_with('-') => return Err(format!("opción desconocida: {}", arg)),
+            _ => opciones.entradas.push(PathBuf::from(arg)),
+        }
+    }
+
+    if opciones.entradas.is_empty() {
+        return Err("indica al menos un archivo o directorio".to_string());
+    }
+    opciones.maquetacion.validar().map_err(|e| e.to_string())?;
+    Ok(Some(opciones))
+}
+
+/// Añade a `libros` cada .epub de `entrada`, con su ruta relativa a ella (para
+/// reproducir la estructura de directorios en `--salida`). Los enlaces
+/// simbólicos se siguen, pero cada directorio se recorre una sola vez: un
+/// enlace que apunta a un directorio padre no hace un ciclo.
+fn buscar_epubs(entrada: &Path, libros: &mut Vec<(PathBuf, PathBuf)>) -> Result<(), String> {
+    if entrada.is_file() {
+        let nombre = entrada.file_name().map(PathBuf::from).unwrap_or_default();
+        libros.push((entrada.to_path_buf(), nombre));
+        return Ok(());
+    }
+    if !entrada.is_dir() {
+        return Err(format!("no existe: {}", entrada.display()));
+    }
+
+    let mut pendientes = vec![entrada.to_path_buf()];
+    let mut visitados = HashSet::new();
+    let mut encontrados = Vec::new();
+    while let Some(dir) = pendientes.pop() {
+        if !visitados.insert(fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone())) {
+            continue;
+        }
+        let lectura = fs::read_dir(&dir)
+            .map_err(|e| format!("no se pudo leer {}: {}", dir.display(), e))?;
+        for ruta in lectura.flatten().map(|e| e.path()) {
+            if ruta.is_dir() {
+                pendientes.push(ruta);
+            } else if ruta.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("epub")) {
+                let relativa = ruta.strip_prefix(entrada).unwrap_or(&ruta).to_path_buf();
+                encontrados.push((ruta, relativa));
+            }
+        }
+    }
+    encontrados.sort();
+    libros.extend(encontrados);
+    Ok(())
+}
+
+/// Archivo de salida de cada libro: junto al .epub o, con `--salida`, en la
+/// misma ruta relativa dentro de ese directorio. Dos libros que irían al
+/// mismo archivo (a/libro.epub y b/libro.epub pasados como archivos) son un
+/// error de uso: el segundo borraría el primero.
+fn destinos(
+    libros: &[(PathBuf, PathBuf)],
+    opciones: &Opciones,
+) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let mut vistos: HashMap<PathBuf, &Path> = HashMap::new();
+    let mut destinos = Vec::with_capacity(libros.len());
+    for (epub, relativa) in libros {
+        let destino = match &opciones.salida {
+            None => PathBuf::from(braille::ruta_salida(&epub.display().to_string(), opciones.formato)),
+            Some(dir) => {
+                let nombre = format!("{}_braille.{}",
+                    relativa.file_stem().unwrap_or_default().to_string_lossy(),
+                    opciones.formato.extension());
+                dir.join(relativa).with_file_name(nombre)
+            }
+        };
+        if let Some(otro) = vistos.insert(destino.clone(), epub) {
+            return Err(format!("{} y {} se escribirían en el mismo archivo: {}",
+                otro.display(), epub.display(), destino.display()));
+        }
+        destinos.push((epub.clone(), destino));
+    }
+    Ok(destinos)
+}
+
+fn convertir(epub: &Path, destino: &Path, opciones: &Opciones) -> Resultado {
+    let entrada = epub.display().to_string();
+    let resultado = crear_directorio(destino).and_then(|_| {
+        braille::convertir_epub_en(
+            &entrada,
+            &destino.display().to_string(),
+            opciones.formato,
+            &opciones.maquetacion,
+            &opciones.codificacion,
+            opciones.tabla.as_deref(),
+        )
+    });
+
+    match resultado {
+        Ok(conversion) => {
+            eprintln!("✓ {} → {}", entrada, conversion.ruta);
+            avisar_sin_celda(&conversion.informe);
+            Resultado { entrada, conversion: Some(conversion), error: None }
+        }
+        Err(error) => {
+            eprintln!("✗ {}: {}", entrada, error);
+            Resultado { entrada, conversion: None, error: Some(error) }
+        }
+    }
+}
+
+/// Una línea en stderr con los caracteres que la tabla no supo codificar.
+fn avisar_sin_celda(informe: &Informe) {
+    if informe.sin_celda.is_empty() {
+        return;
+    }
+    let lista: Vec<String> = informe.sin_celda.iter()
+        .map(|s| format!("{} ({}) ×{}", s.caracter, s.codigo, s.cantidad))
+        .collect();
+    eprintln!("  {} caracteres sin celda en la tabla: {}",
+        informe.total_sin_celda(), lista.join(", "));
+}
+
+fn crear_directorio(destino: &Path) -> Result<(), ErrorBraille> {
+    match destino.parent() {
+        Some(dir) => fs::create_dir_all(dir).map_err(|e| ErrorBraille::Escritura {
+            ruta: dir.display().to_string(),
+            detalle: e.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opciones(args: &[&str]) -> Opciones {
+        match leer_argumentos(args.iter().map(|a| a.to_string())) {
+            Ok(Some(opciones)) => opciones,
+            Ok(None) => panic!("se pidió la ayuda"),
+            Err(mensaje) => panic!("{}", mensaje),
+        }
+    }
+
+    /// Directorio temporal vacío, propio de cada prueba.
+    fn directorio_temporal(nombre: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("epub2braille-{}-{}", nombre, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn enlace_circular() {
+        let dir = directorio_temporal("enlace");
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/libro.epub"), b"").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("sub/arriba")).unwrap();
+
+        let mut libros = Vec::new();
+        buscar_epubs(&dir, &mut libros).unwrap();
+        assert_eq!(libros, [(dir.join("sub/libro.epub"), PathBuf::from("sub/libro.epub"))]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn destinos_en_el_directorio_de_salida() {
+        let libros = [
+            (PathBuf::from("a/libro.epub"), PathBuf::from("libro.epub")),
+            (PathBuf::from("cat/sub/otro.epub"), PathBuf::from("sub/otro.epub")),
+        ];
+        let destinos = destinos(&libros, &opciones(&["-o", "out", "-f", "brf", "x"])).unwrap();
+        assert_eq!(destinos[0].1, PathBuf::from("out/libro_braille.brf"));
+        assert_eq!(destinos[1].1, PathBuf::from("out/sub/otro_braille.brf"));
+
+        let destinos = super::destinos(&libros, &opciones(&["x"])).unwrap();
+        assert_eq!(destinos[0].1, PathBuf::from("a/libro_braille.bin"));
+    }
+
+    #[test]
+    fn destinos_repetidos() {
+        let libros = [
+            (PathBuf::from("a/libro.epub"), PathBuf::from("libro.epub")),
+            (PathBuf::from("b/libro.epub"), PathBuf::from("libro.epub")),
+        ];
+        let error = destinos(&libros, &opciones(&["-o", "out", "x"])).unwrap_err();
+        assert!(error.contains("a/libro.epub y b/libro.epub"), "{}", error);
+        // Sin --salida cada uno queda junto a su .epub
+        assert!(destinos(&libros, &opciones(&["x"])).is_ok());
+    }
+
+    fn error(args: &[&str]) -> Option<String> {
+        leer_argumentos(args.iter().map(|a| a.to_string())).err()
+    }
+
+    #[test]
+    fn argumentos() {
+        let leidas = opciones(&["-f", "pef", "--ancho", "32", "a.epub", "libros/", "--partir-hiatos"]);
+        assert_eq!(leidas.entradas, [PathBuf::from("a.epub"), PathBuf::from("libros/")]);
+        assert!(matches!(leidas.formato, FormatoSalida::Pef));
+        assert_eq!(leidas.maquetacion.celdas_por_linea, 32);
+        assert!(leidas.codificacion.division.hiatos);
+        assert!(leidas.salida.is_none());
+        assert!(leidas.tabla.is_none());
+    }
+
+    #[test]
+    fn ayuda() {
+        for ayuda in ["-h", "--help"] {
+            assert!(matches!(leer_argumentos([ayuda.to_string()].into_iter()), Ok(None)));
+        }
+        // La ayuda gana aunque falten las entradas o sobren opciones
+        assert!(matches!(leer_argumentos(["-f", "brf", "-h"].map(String::from).into_iter()), Ok(None)));
+    }
+
+    #[test]
+    fn errores_de_uso() {
+        assert_eq!(error(&["--ancha", "x"]).unwrap(), "opción desconocida: --ancha");
+        assert_eq!(error(&["x", "-o"]).unwrap(), "falta el valor de -o");
+        assert_eq!(error(&["x", "--formato"]).unwrap(), "falta el valor de --formato");
+        assert_eq!(error(&["-f", "docx", "x"]).unwrap(), "formato desconocido: docx");
+        assert_eq!(error(&["-w", "ancho", "x"]).unwrap(), "ancho no válido: ancho");
+        assert_eq!(error(&["-d", "borrar", "x"]).unwrap(), "modo desconocido: borrar");
+        assert_eq!(error(&[]).unwrap(), "indica al menos un archivo o directorio");
+        assert_eq!(error(&["-f", "brf"]).unwrap(), "indica al menos un archivo o directorio");
+    }
+
+    #[test]
+    fn resaltes() {
+        let enfasis = opciones(&["-r", "cursiva, subrayado", "x"]).codificacion.enfasis;
+        assert!(enfasis.cursiva && !enfasis.negrita && enfasis.subrayado);
+        let enfasis = opciones(&["-r", "ninguno", "x"]).codificacion.enfasis;
+        assert!(!enfasis.cursiva && !enfasis.negrita && !enfasis.subrayado);
+        let enfasis = opciones(&["x"]).codificacion.enfasis;
+        assert!(enfasis.cursiva && enfasis.negrita && enfasis.subrayado);
+        assert_eq!(error(&["-r", "cursiva,versalita", "x"]).unwrap(), "resalte desconocido: versalita");
+    }
+
+    #[test]
+    fn ancho_no_valido() {
+        assert!(error(&["-w", "0", "x"]).is_some_and(|e| e.contains("al menos")));
+        assert!(error(&["-w", "1", "x"]).is_some());
+        // Con 2 celdas no cabe la sangría de párrafo
+        assert!(error(&["-w", "2", "x"]).is_some_and(|e| e.contains("sangría")));
+        assert!(error(&["-w", "3", "x"]).is_none());
+    }
+}
@@ -0,0 +1,63 @@
+// ─── BRF: North American ASCII Braille ───────────────────────────────────────
+// Formato de texto plano que abren Duxbury/BrailleBlaster y aceptan los
+// embosadores comerciales: un carácter ASCII por celda, CR/LF al final de
+// cada línea y salto de página (form feed) entre una página y la siguiente.
+
+use super::Pagina;
+
+/// Carácter ASCII Braille indexado por el byte de la celda (bit 0 = punto 1).
+const ASCII_BRAILLE: &[u8; 64] =
+    b" A1B'K2L@CIF/MSP\"E3H9O6R^DJG>NTQ,*5<-U8V.%[$+X!&;:4\\0Z7(_?W]#Y)=";
+
+const FIN_LINEA: &str = "\r\n";
+const SALTO_PAGINA: char = '\u{000C}';
+
+fn byte_a_ascii(byte: u8) -> char {
+    ASCII_BRAILLE[(byte & 0x3F) as usize] as char
+}
+
+/// Convierte las páginas formateadas a BRF. Los espacios del final de cada
+/// línea se recortan (el relleno solo tiene sentido en el .bin). Tras la
+/// última página no va salto: el embosador no saca una hoja en blanco.
+pub fn paginas_a_brf(paginas: &[Pagina]) -> String {
+    let mut brf = String::new();
+    for (k, pagina) in paginas.iter().enumerate() {
+        if k > 0 {
+            brf.push(SALTO_PAGINA);
+        }
+        for linea in &pagina.lineas {
+            let texto: String = linea.iter().map(|&b| byte_a_ascii(b)).collect();
+            brf.push_str(texto.trim_end_matches(' '));
+            brf.push_str(FIN_LINEA);
+        }
+    }
+    brf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puntos_a_byte;
+
+    #[test]
+    fn celdas_en_ascii_braille() {
+        assert_eq!(byte_a_ascii(0), ' ');
+        assert_eq!(byte_a_ascii(puntos_a_byte(&[1])), 'A');
+        assert_eq!(byte_a_ascii(puntos_a_byte(&[3, 4, 5, 6])), '#');
+        assert_eq!(byte_a_ascii(puntos_a_byte(&[1, 2, 3, 4, 5, 6])), '=');
+        assert_eq!(byte_a_ascii(puntos_a_byte(&[4, 6])), '.');
+    }
+
+    #[test]
+    fn lineas_y_paginas() {
+        let a = puntos_a_byte(&[1]);
+        let numero = puntos_a_byte(&[3, 4, 5, 6]);
+        let paginas = [
+            Pagina { numero: 1, lineas: vec![vec![a, 0, a, 0, 0], vec![0; 5]] },
+            Pagina { numero: 2, lineas: vec![vec![numero, a, 0, 0, 0]] },
+        ];
+        // El relleno se recorta; el form feed solo separa las páginas
+        assert_eq!(paginas_a_brf(&paginas), "A A\r\n\r\n\u{c}#A\r\n");
+        assert_eq!(paginas_a_brf(&paginas[..1]), "A A\r\n\r\n");
+    }
+}
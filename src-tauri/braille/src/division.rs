@@ -0,0 +1,211 @@
+// ─── División de palabras a final de línea ───────────────────────────────────
+// El silabeador marca todas las fronteras de sílaba, pero no todas sirven
+// para partir una palabra al final de la línea. La Ortografía de la RAE no
+// deja una letra sola a final ni a comienzo de línea (a-ho-ra, rí-o),
+// desaconseja separar vocales en hiato aunque medie una h muda (le-er,
+// bú-ho) y parte las palabras compuestas con guion solo por el guion
+// (franco-alemán), que el formateador repite al comienzo de la línea
+// siguiente. Los nombres propios y las siglas no se parten. El formateador
+// solo ve los cortes que quedan.
+
+use serde::{Deserialize, Serialize};
+
+/// Letras que deben quedar a cada lado del corte, por defecto.
+const FRAGMENTO_MINIMO: usize = 2;
+
+/// Vocales a los dos lados de un corte: el silabeador partió un hiato.
+const VOCALES: &str = "aeiouáéíóúüàèìòùâêôãõäëïö";
+
+/// Guiones que unen las partes de una palabra compuesta.
+const GUIONES: &str = "-\u{2010}\u{2011}";
+
+/// Signos que pueden preceder a la primera palabra de una frase.
+const APERTURAS: &str = "¿¡«“‘\"'([{—–-";
+
+/// Signos tras los que empieza una frase.
+const CIERRES_DE_FRASE: &str = ".!?…:";
+
+/// Qué fronteras de sílaba se aceptan como corte a final de línea.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Division {
+    /// Letras que deben quedar, como mínimo, a cada lado del corte (con 2,
+    /// nunca a-ho-ra ni rí-o).
+    pub fragmento_minimo: usize,
+    /// Partir vocales en hiato (le-er, bú-ho).
+    pub hiatos: bool,
+    /// Partir nombres propios y siglas.
+    pub nombres_propios: bool,
+}
+
+impl Default for Division {
+    fn default() -> Self {
+        Division { fragmento_minimo: FRAGMENTO_MINIMO, hiatos: false, nombres_propios: false }
+    }
+}
+
+impl Division {
+    /// Quita de `cortes` (uno por letra de `letras`, verdadero donde empieza
+    /// una sílaba) los que no sirven para partir la palabra. `antes` y
+    /// `despues` son el texto que la rodea; en un pasaje en mayúsculas
+    /// (`en_pasaje`) una palabra en mayúsculas no es una sigla.
+    pub(crate) fn filtrar(
+        &self,
+        letras: &[char],
+        cortes: &mut [bool],
+        antes: &[char],
+        despues: &[char],
+        en_pasaje: bool,
+    ) {
+        let compuesta = unida_por_guion(antes.iter().rev()) || unida_por_guion(despues.iter());
+        let intacta = compuesta
+            || (!self.nombres_propios && (es_sigla(letras, en_pasaje) || es_nombre_propio(letras, antes)));
+        let n = letras.len();
+        for (k, corte) in cortes.iter_mut().enumerate() {
+            if !*corte {
+                continue;
+            }
+            let corto = k < self.fragmento_minimo || n - k < self.fragmento_minimo;
+            let hiato = !self.hiatos && es_hiato(letras, k);
+            if intacta || corto || hiato {
+                *corte = false;
+            }
+        }
+    }
+}
+
+/// ¿Hay un guion y tras él una letra, mirando desde la palabra hacia fuera?
+fn unida_por_guion<'a>(mut lado: impl Iterator<Item = &'a char>) -> bool {
+    lado.next().is_some_and(|c| es_guion(*c)) && lado.next().is_some_and(|c| c.is_alphabetic())
+}
+
+/// ¿Es `c` uno de los guiones que unen las partes de una compuesta?
+pub(crate) fn es_guion(c: char) -> bool {
+    GUIONES.contains(c)
+}
+
+fn es_vocal(c: char) -> bool {
+    c.to_lowercase().all(|c| VOCALES.contains(c))
+}
+
+/// ¿Separa el corte ante la letra `k` dos vocales, con o sin una h entre
+/// ellas (le-er, bú-ho)?
+fn es_hiato(letras: &[char], k: usize) -> bool {
+    if k == 0 || !es_vocal(letras[k - 1]) {
+        return false;
+    }
+    match letras[k] {
+        'h' | 'H' => letras.get(k + 1).is_some_and(|&c| es_vocal(c)),
+        c => es_vocal(c),
+    }
+}
+
+/// Palabra de varias letras, todas mayúsculas, fuera de un pasaje en
+/// mayúsculas (ONCE, UNESCO).
+fn es_sigla(letras: &[char], en_pasaje: bool) -> bool {
+    !en_pasaje && letras.len() > 1 && letras.iter().all(|c| c.is_uppercase())
+}
+
+/// Palabra con mayúscula inicial que no abre una frase (Valladolid, pero no
+/// Ahora a comienzo de párrafo).
+fn es_nombre_propio(letras: &[char], antes: &[char]) -> bool {
+    letras.first().is_some_and(|c| c.is_uppercase())
+        && letras.iter().skip(1).any(|c| c.is_lowercase())
+        && !abre_frase(antes)
+}
+
+/// ¿Empieza una frase tras `antes`? Sí al comienzo del texto o de una línea
+/// y tras un punto, aunque haya espacios o signos de apertura entre medias.
+fn abre_frase(antes: &[char]) -> bool {
+    for &c in antes.iter().rev() {
+        if matches!(c, '\n' | '\r' | '\u{2028}') {
+            return true;
+        }
+        if c.is_whitespace() || APERTURAS.contains(c) {
+            continue;
+        }
+        return CIERRES_DE_FRASE.contains(c);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::silabear;
+
+    /// La palabra con los cortes que admite `division`, en el contexto
+    /// `antes` + palabra + `despues`.
+    fn cortes(division: &Division, antes: &str, palabra: &str, despues: &str) -> String {
+        let letras: Vec<char> = palabra.chars().collect();
+        let silabas = silabear(palabra);
+        let mut marcas: Vec<bool> = palabra.char_indices()
+            .map(|(b, _)| b > 0 && silabas.contains(&b))
+            .collect();
+        let antes: Vec<char> = antes.chars().collect();
+        let despues: Vec<char> = despues.chars().collect();
+        division.filtrar(&letras, &mut marcas, &antes, &despues, false);
+        letras.iter().zip(&marcas)
+            .flat_map(|(&c, &corte)| if corte { vec!['-', c] } else { vec![c] })
+            .collect()
+    }
+
+    #[test]
+    fn nunca_una_letra_sola() {
+        let d = Division::default();
+        assert_eq!(cortes(&d, "", "ahora", ""), "aho-ra");
+        assert_eq!(cortes(&d, "", "río", ""), "río");
+        assert_eq!(cortes(&d, "", "aéreo", ""), "aé-reo");
+        assert_eq!(cortes(&d, "", "caminaba", ""), "ca-mi-na-ba");
+        let tres = Division { fragmento_minimo: 3, ..Division::default() };
+        assert_eq!(cortes(&tres, "", "caminaba", ""), "cami-naba");
+        let sin_limite = Division { fragmento_minimo: 1, hiatos: true, ..Division::default() };
+        assert_eq!(cortes(&sin_limite, "", "ahora", ""), "a-ho-ra");
+        assert_eq!(cortes(&sin_limite, "", "río", ""), "rí-o");
+    }
+
+    #[test]
+    fn hiatos() {
+        let d = Division::default();
+        assert_eq!(cortes(&d, "", "poeta", ""), "poe-ta");
+        assert_eq!(cortes(&d, "", "leerlo", ""), "leer-lo");
+        assert_eq!(cortes(&d, "", "búho", ""), "búho");
+        assert_eq!(cortes(&d, "", "alcohol", ""), "al-cohol");
+        // Con h entre consonante y vocal no hay hiato
+        assert_eq!(cortes(&d, "", "deshacer", ""), "des-ha-cer");
+        let con_hiatos = Division { hiatos: true, ..Division::default() };
+        assert_eq!(cortes(&con_hiatos, "", "poeta", ""), "po-e-ta");
+        assert_eq!(cortes(&con_hiatos, "", "alcohol", ""), "al-co-hol");
+    }
+
+    #[test]
+    fn compuestas_con_guion() {
+        let d = Division::default();
+        assert_eq!(cortes(&d, "", "franco", "-alemán"), "franco");
+        assert_eq!(cortes(&d, "franco-", "alemán", ""), "alemán");
+        assert_eq!(cortes(&d, "teórico\u{2010}", "práctico", ""), "práctico");
+        // Un guion que no une dos palabras no las hace compuestas
+        assert_eq!(cortes(&d, "", "camino", "- dijo"), "ca-mi-no");
+        assert_eq!(cortes(&d, "— ", "camino", ""), "ca-mi-no");
+    }
+
+    #[test]
+    fn nombres_propios_y_siglas() {
+        let d = Division::default();
+        assert_eq!(cortes(&d, "vive en ", "Valladolid", ""), "Valladolid");
+        assert_eq!(cortes(&d, "la ", "UNESCO", ""), "UNESCO");
+        // A comienzo de frase, la mayúscula no hace de la palabra un nombre
+        assert_eq!(cortes(&d, "", "Caminaba", ""), "Ca-mi-na-ba");
+        assert_eq!(cortes(&d, "Llegó. ", "Caminaba", ""), "Ca-mi-na-ba");
+        assert_eq!(cortes(&d, "lejos.\n«¿", "Caminaba", ""), "Ca-mi-na-ba");
+        // Pero en un pasaje en mayúsculas no son siglas
+        let mut marcas = vec![false, false, true, false, true, false, false];
+        let letras: Vec<char> = "CAMINAS".chars().collect();
+        d.filtrar(&letras, &mut marcas, &[], &[], true);
+        assert_eq!(marcas, [false, false, true, false, true, false, false]);
+
+        let todas = Division { nombres_propios: true, ..Division::default() };
+        assert_eq!(cortes(&todas, "vive en ", "Valladolid", ""), "Va-lla-do-lid");
+        assert_eq!(cortes(&todas, "la ", "UNESCO", ""), "UNES-CO");
+    }
+}
@@ -0,0 +1,350 @@
+// ─── Modelo de documento: bloques en orden de lectura ────────────────────────
+// El XHTML de cada capítulo se recorre entero (no solo los <p>) y se reduce a
+// una lista de bloques tipados. Es la misma estructura que usa el editor, que
+// la representa como texto con una marca mínima al comienzo de línea:
+//   "# Título"   → encabezado (un '#' por nivel, hasta 6)
+//   "• Elemento" → elemento de lista; la viñeta es parte del texto y se
+//                  escribe en braille
+// Cualquier otra línea no vacía es un párrafo, también las que empiezan por
+// un guion: así se escriben los diálogos ("- Hola -dijo él.").
+
+use scraper::{ElementRef, Html, Node, Selector};
+
+use super::SEPARADOR_LINEA;
+use crate::resalte::{self, EstilosCss, Resalte};
+
+/// Viñeta de los elementos de listas no ordenadas.
+pub const VINETA: char = '\u{2022}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TipoBloque {
+    /// `<h1>`–`<h6>`, con su nivel.
+    Encabezado(u8),
+    Parrafo,
+    /// `<li>`; el texto ya incluye la viñeta ("• …") o, en listas
+    /// ordenadas, el número ("3. …").
+    ElementoLista,
+    /// `<blockquote>` y todo lo que contiene.
+    Cita,
+    /// `<pre>`: los saltos de línea originales se conservan como U+2028.
+    Preformateado,
+    /// `<td>` / `<th>`, una celda por bloque en orden de lectura.
+    CeldaTabla,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bloque {
+    pub tipo: TipoBloque,
+    pub texto: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Documento {
+    pub bloques: Vec<Bloque>,
+    /// Índice del primer bloque de cada capítulo; vacío si es uno solo.
+    pub capitulos: Vec<usize>,
+}
+
+impl Documento {
+    /// Recorre el `<body>` de un capítulo XHTML.
+    pub fn desde_xhtml(content: &str) -> Documento {
+        Documento::desde_xhtml_con_estilos(content, &EstilosCss::default())
+    }
+
+    /// Igual que `desde_xhtml`, con las hojas de estilo del libro para
+    /// reconocer las clases de resalte. Los `<style>` del capítulo se añaden
+    /// a ellas.
+    pub fn desde_xhtml_con_estilos(content: &str, estilos: &EstilosCss) -> Documento {
+        let html = Html::parse_document(content);
+        let body = Selector::parse("body").unwrap();
+        let raiz = html.select(&body).next().unwrap_or_else(|| html.root_element());
+
+        let mut estilos = estilos.clone();
+        for style in html.select(&Selector::parse("style").unwrap()) {
+            estilos.anadir(&style.text().collect::<String>());
+        }
+        let mut recorrido = Recorrido { estilos, ..Recorrido::default() };
+        recorrido.hijos(raiz, TipoBloque::Parrafo, false);
+        recorrido.cerrar(TipoBloque::Parrafo, false);
+        Documento { bloques: recorrido.bloques, ..Documento::default() }
+    }
+
+    /// Interpreta el texto del editor (ver marcas al comienzo del módulo).
+    pub fn desde_texto(texto: &str) -> Documento {
+        let bloques = texto.lines()
+            .map(str::trim_end)
+            .filter(|l| !l.trim().is_empty())
+            .map(|linea| {
+                let almohadillas = linea.chars().take_while(|&c| c == '#').count();
+                if (1..=6).contains(&almohadillas) && linea[almohadillas..].starts_with(' ') {
+                    Bloque {
+                        tipo: TipoBloque::Encabezado(almohadillas as u8),
+                        texto: linea[almohadillas..].trim().to_string(),
+                    }
+                } else if linea.starts_with(VINETA) {
+                    Bloque { tipo: TipoBloque::ElementoLista, texto: linea.trim().to_string() }
+                } else {
+                    Bloque { tipo: TipoBloque::Parrafo, texto: linea.to_string() }
+                }
+            })
+            .map(|bloque| Bloque { texto: resalte::quitar_marcas(&bloque.texto), ..bloque })
+            .collect();
+        Documento { bloques, ..Documento::default() }
+    }
+
+    /// Texto para el editor: un bloque por línea, con la marca de
+    /// encabezado. Los elementos de lista ya llevan su viñeta; los de listas
+    /// ordenadas vuelven como párrafos que empiezan por su número.
+    pub fn a_texto(&self) -> String {
+        let mut texto = String::new();
+        for bloque in &self.bloques {
+            if let TipoBloque::Encabezado(nivel) = bloque.tipo {
+                texto.push_str(&"#".repeat(nivel as usize));
+                texto.push(' ');
+            }
+            texto.push_str(&resalte::quitar_marcas(&bloque.texto));
+            texto.push('\n');
+        }
+        texto
+    }
+
+    /// Añade otro documento como capítulo nuevo.
+    pub fn anadir_capitulo(&mut self, capitulo: Documento) {
+        self.capitulos.push(self.bloques.len());
+        self.bloques.extend(capitulo.bloques);
+    }
+
+    /// Capítulo del bloque con ese índice y su número dentro de él, ambos
+    /// desde 1.
+    pub fn posicion_bloque(&self, indice: usize) -> (usize, usize) {
+        let capitulo = self.capitulos.partition_point(|&inicio| inicio <= indice);
+        let inicio = capitulo.checked_sub(1).map_or(0, |c| self.capitulos[c]);
+        (capitulo.max(1), indice - inicio + 1)
+    }
+}
+
+// ─── Recorrido del árbol XHTML ───────────────────────────────────────────────
+
+#[derive(Default)]
+struct Recorrido {
+    bloques: Vec<Bloque>,
+    /// Texto acumulado del bloque en curso.
+    buffer: String,
+    /// Viñeta o número de un elemento de lista, a la espera del primer texto.
+    prefijo: Option<String>,
+    /// Elementos abiertos de cada clase de resalte (ver `Resalte`); la marca
+    /// va al abrir el primero y al cerrar el último.
+    resaltes: [usize; 3],
+    estilos: EstilosCss,
+}
+
+impl Recorrido {
+    fn hijos(&mut self, el: ElementRef, contexto: TipoBloque, pre: bool) {
+        let ordenada = el.value().name() == "ol";
+        let lista = ordenada || el.value().name() == "ul";
+        let mut numero_item = 0u32;
+
+        for hijo in el.children() {
+            match hijo.value() {
+                // Los caracteres de las marcas que traiga el propio libro
+                // (fuentes de iconos) no deben abrir un resalte
+                Node::Text(t) => self.buffer.extend(t.chars().filter(|&c| !resalte::es_marca(c))),
+                Node::Element(_) => {
+                    if let Some(e) = ElementRef::wrap(hijo) {
+                        if lista && e.value().name() == "li" {
+                            numero_item += 1;
+                            self.prefijo = Some(if ordenada {
+                                format!("{}. ", numero_item)
+                            } else {
+                                format!("{} ", VINETA)
+                            });
+                        }
+                        self.elemento(e, contexto, pre);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn elemento(&mut self, e: ElementRef, contexto: TipoBloque, pre: bool) {
+        let nombre = e.value().name();
+        let mut resaltes: Vec<Resalte> = e.value().attr("class")
+            .map(|clases| self.estilos.de_clases(nombre, clases))
+            .unwrap_or_default();
+        let del_estilo = e.value().attr("style").map(Resalte::de_estilo).unwrap_or_default();
+        for r in del_estilo.into_iter().chain(Resalte::de_etiqueta(nombre)) {
+            if !resaltes.contains(&r) {
+                resaltes.push(r);
+            }
+        }
+        let tipo = match nombre {
+            "script" | "style" | "head" | "title" | "noscript" | "template" => return,
+            "br" => {
+                self.buffer.push(SEPARADOR_LINEA);
+                return;
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                TipoBloque::Encabezado(nombre.as_bytes()[1] - b'0')
+            }
+            "li" => TipoBloque::ElementoLista,
+            "blockquote" => TipoBloque::Cita,
+            "pre" => TipoBloque::Preformateado,
+            "td" | "th" => TipoBloque::CeldaTabla,
+            // Bloques que heredan el tipo del contenedor: un <p> dentro de
+            // <blockquote> sigue siendo cita; dentro de <li>, elemento de lista.
+            "p" | "div" | "section" | "article" | "header" | "footer" | "aside"
+            | "nav" | "main" | "figure" | "figcaption" | "address" | "center"
+            | "ul" | "ol" | "dl" | "dt" | "dd" | "table" | "thead" | "tbody"
+            | "tfoot" | "tr" | "caption" | "hr" => contexto,
+            // Elementos en línea (<span>, <em>, <a>…): su texto va al bloque en curso
+            _ => {
+                self.abrir_resaltes(&resaltes);
+                self.hijos(e, contexto, pre);
+                self.cerrar_resaltes(&resaltes);
+                return;
+            }
+        };
+
+        // El texto suelto que el contenedor tuviera antes de este bloque
+        self.cerrar(contexto, pre);
+        let pre = pre || nombre == "pre";
+        self.abrir_resaltes(&resaltes);
+        self.hijos(e, tipo, pre);
+        self.cerrar_resaltes(&resaltes);
+        self.cerrar(tipo, pre);
+        if nombre == "li" {
+            self.prefijo = None; // elemento vacío: la marca no pasa al siguiente bloque
+        }
+    }
+
+    fn abrir_resaltes(&mut self, resaltes: &[Resalte]) {
+        for &r in resaltes {
+            if self.resaltes[r.indice()] == 0 {
+                self.buffer.push(r.apertura());
+            }
+            self.resaltes[r.indice()] += 1;
+        }
+    }
+
+    fn cerrar_resaltes(&mut self, resaltes: &[Resalte]) {
+        for &r in resaltes.iter().rev() {
+            self.resaltes[r.indice()] -= 1;
+            if self.resaltes[r.indice()] == 0 {
+                self.buffer.push(r.cierre());
+            }
+        }
+    }
+
+    /// Convierte el texto acumulado en un bloque del tipo indicado. Los
+    /// resaltes que siguen abiertos se cierran en este bloque y se vuelven a
+    /// abrir en el siguiente.
+    fn cerrar(&mut self, tipo: TipoBloque, pre: bool) {
+        let abiertos: Vec<Resalte> = Resalte::TODOS.into_iter()
+            .filter(|&r| self.resaltes[r.indice()] > 0)
+            .collect();
+        let mut crudo = std::mem::take(&mut self.buffer);
+        crudo.extend(abiertos.iter().rev().map(|r| r.cierre()));
+        self.buffer.extend(abiertos.iter().map(|r| r.apertura()));
+        let crudo = resalte::cenir_marcas(&crudo);
+
+        let texto = if pre {
+            crudo.trim_matches('\n')
+                .replace("\r\n", "\n")
+                .replace('\n', &SEPARADOR_LINEA.to_string())
+        } else {
+            normalizar_espacios(&crudo)
+        };
+        if texto.chars().all(|c| c.is_whitespace() || resalte::es_marca(c)) {
+            return;
+        }
+
+        let texto = match self.prefijo.take() {
+            Some(prefijo) => prefijo + &texto,
+            None => texto,
+        };
+        self.bloques.push(Bloque { tipo, texto });
+    }
+}
+
+/// Colapsa los espacios como haría un navegador, respetando los `<br>`
+/// (U+2028, que `split_whitespace` también consideraría espacio).
+fn normalizar_espacios(texto: &str) -> String {
+    let lineas: Vec<String> = texto.split(SEPARADOR_LINEA)
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    let inicio = lineas.iter().position(|l| !l.is_empty()).unwrap_or(lineas.len());
+    let fin = lineas.iter().rposition(|l| !l.is_empty()).map_or(inicio, |p| p + 1);
+    lineas[inicio..fin].join(&SEPARADOR_LINEA.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bloques(documento: &Documento) -> Vec<(TipoBloque, &str)> {
+        documento.bloques.iter().map(|b| (b.tipo, b.texto.as_str())).collect()
+    }
+
+    #[test]
+    fn encabezados() {
+        let doc = Documento::desde_xhtml(
+            "<html><head><title>No</title></head><body>\
+             <h1>Uno</h1><h3>Tres <span>y</span> medio</h3><p>Texto</p></body></html>");
+        assert_eq!(bloques(&doc), [
+            (TipoBloque::Encabezado(1), "Uno"),
+            (TipoBloque::Encabezado(3), "Tres y medio"),
+            (TipoBloque::Parrafo, "Texto"),
+        ]);
+    }
+
+    #[test]
+    fn listas() {
+        let doc = Documento::desde_xhtml(
+            "<body><ul><li>pan</li><li><p>vino</p></li><li></li></ul>\
+             <ol><li>primero</li><li>segundo</li></ol><p>fin</p></body>");
+        assert_eq!(bloques(&doc), [
+            (TipoBloque::ElementoLista, "• pan"),
+            (TipoBloque::ElementoLista, "• vino"),
+            (TipoBloque::ElementoLista, "1. primero"),
+            (TipoBloque::ElementoLista, "2. segundo"),
+            (TipoBloque::Parrafo, "fin"),
+        ]);
+    }
+
+    #[test]
+    fn citas() {
+        let doc = Documento::desde_xhtml(
+            "<body><p>Antes</p><blockquote><p>Uno</p>suelto<p>Dos</p></blockquote></body>");
+        assert_eq!(bloques(&doc), [
+            (TipoBloque::Parrafo, "Antes"),
+            (TipoBloque::Cita, "Uno"),
+            (TipoBloque::Cita, "suelto"),
+            (TipoBloque::Cita, "Dos"),
+        ]);
+    }
+
+    #[test]
+    fn texto_del_editor() {
+        let doc = Documento::desde_texto("## Título\n• pan\n- Hola -dijo él.\n\n#sin espacio\n");
+        assert_eq!(bloques(&doc), [
+            (TipoBloque::Encabezado(2), "Título"),
+            (TipoBloque::ElementoLista, "• pan"),
+            (TipoBloque::Parrafo, "- Hola -dijo él."),
+            (TipoBloque::Parrafo, "#sin espacio"),
+        ]);
+        assert_eq!(doc.a_texto(), "## Título\n• pan\n- Hola -dijo él.\n#sin espacio\n");
+    }
+
+    #[test]
+    fn dialogo_y_vineta_en_braille() {
+        use crate::{codificar_documento, puntos_a_byte, OpcionesCodificacion, TablaEspanol};
+        let doc = Documento::desde_texto("- Hola\n• pan");
+        let (celdas, _) = codificar_documento(&doc, &TablaEspanol, &OpcionesCodificacion::default());
+        let bytes: Vec<u8> = celdas.iter().filter(|c| c.salto.is_none()).map(|c| c.byte).collect();
+        let guion = puntos_a_byte(&[3, 6]);
+        let vineta = [puntos_a_byte(&[4, 5, 6]), puntos_a_byte(&[2, 5, 6])];
+        assert_eq!(bytes[0], guion);
+        assert!(bytes.windows(2).any(|w| w == vineta));
+    }
+}
@@ -0,0 +1,102 @@
+// ─── Errores del módulo braille ──────────────────────────────────────────────
+// Un EPUB mal formado no debe tumbar el comando de Tauri: cada fallo se
+// devuelve a la interfaz como `{ tipo, mensaje }`, con `tipo` estable para
+// poder distinguir casos y `mensaje` listo para mostrar.
+
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[derive(Debug)]
+pub enum ErrorBraille {
+    /// No se pudo abrir el archivo de entrada.
+    Apertura { ruta: String, detalle: String },
+    /// El archivo no es un zip legible.
+    EpubInvalido(String),
+    /// Falta META-INF/container.xml.
+    SinContainer,
+    /// container.xml mal formado o sin `full-path`.
+    ContainerInvalido(String),
+    /// El content.opf declarado no está en el zip.
+    SinOpf(String),
+    /// content.opf mal formado.
+    OpfInvalido { ruta: String, detalle: String },
+    /// Una entrada del EPUB no está en UTF-8.
+    Codificacion(String),
+    /// Un capítulo (u otra entrada del zip) no se pudo leer.
+    CapituloIlegible { ruta: String, detalle: String },
+    /// No se pudo escribir el archivo de salida.
+    Escritura { ruta: String, detalle: String },
+    /// Opciones de maquetación fuera de rango.
+    Maquetacion(String),
+    /// No hay ninguna tabla braille con ese nombre.
+    TablaDesconocida(String),
+    /// Tabla liblouis con una línea que no se puede interpretar.
+    TablaInvalida { tabla: String, linea: usize, detalle: String },
+    /// Archivo de excepciones de silabeo con una línea que no se puede interpretar.
+    ExcepcionesInvalidas { archivo: String, linea: usize, detalle: String },
+}
+
+impl ErrorBraille {
+    /// Identificador estable del caso, para la interfaz.
+    pub fn tipo(&self) -> &'static str {
+        match self {
+            ErrorBraille::Apertura { .. } => "apertura",
+            ErrorBraille::EpubInvalido(_) => "epubInvalido",
+            ErrorBraille::SinContainer => "sinContainer",
+            ErrorBraille::ContainerInvalido(_) => "containerInvalido",
+            ErrorBraille::SinOpf(_) => "sinOpf",
+            ErrorBraille::OpfInvalido { .. } => "opfInvalido",
+            ErrorBraille::Codificacion(_) => "codificacion",
+            ErrorBraille::CapituloIlegible { .. } => "capituloIlegible",
+            ErrorBraille::Escritura { .. } => "escritura",
+            ErrorBraille::Maquetacion(_) => "maquetacion",
+            ErrorBraille::TablaDesconocida(_) => "tablaDesconocida",
+            ErrorBraille::TablaInvalida { .. } => "tablaInvalida",
+            ErrorBraille::ExcepcionesInvalidas { .. } => "excepcionesInvalidas",
+        }
+    }
+}
+
+impl fmt::Display for ErrorBraille {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorBraille::Apertura { ruta, detalle } =>
+                write!(f, "No se pudo abrir {}: {}", ruta, detalle),
+            ErrorBraille::EpubInvalido(detalle) =>
+                write!(f, "No es un EPUB válido: {}", detalle),
+            ErrorBraille::SinContainer =>
+                write!(f, "El EPUB no contiene META-INF/container.xml"),
+            ErrorBraille::ContainerInvalido(detalle) =>
+                write!(f, "container.xml mal formado: {}", detalle),
+            ErrorBraille::SinOpf(ruta) =>
+                write!(f, "No se encontró {} en el EPUB", ruta),
+            ErrorBraille::OpfInvalido { ruta, detalle } =>
+                write!(f, "{} mal formado: {}", ruta, detalle),
+            ErrorBraille::Codificacion(ruta) =>
+                write!(f, "{} no está codificado en UTF-8", ruta),
+            ErrorBraille::CapituloIlegible { ruta, detalle } =>
+                write!(f, "No se pudo leer {} del EPUB: {}", ruta, detalle),
+            ErrorBraille::Escritura { ruta, detalle } =>
+                write!(f, "No se pudo escribir {}: {}", ruta, detalle),
+            ErrorBraille::Maquetacion(detalle) => write!(f, "{}", detalle),
+            ErrorBraille::TablaDesconocida(nombre) =>
+                write!(f, "No hay ninguna tabla braille llamada «{}»", nombre),
+            ErrorBraille::TablaInvalida { tabla, linea, detalle } =>
+                write!(f, "{}, línea {}: {}", tabla, linea, detalle),
+            ErrorBraille::ExcepcionesInvalidas { archivo, linea, detalle } =>
+                write!(f, "{}, línea {}: {}", archivo, linea, detalle),
+        }
+    }
+}
+
+impl std::error::Error for ErrorBraille {}
+
+impl Serialize for ErrorBraille {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ErrorBraille", 2)?;
+        s.serialize_field("tipo", self.tipo())?;
+        s.serialize_field("mensaje", &self.to_string())?;
+        s.end()
+    }
+}
@@ -0,0 +1,268 @@
+// ─── Excepciones de silabeo ──────────────────────────────────────────────────
+// Las reglas fonéticas no saben de morfología: "subrayar" se lee sub-ra-yar
+// (la b y la r no forman grupo), pero la regla del grupo br da su-bra-yar.
+// Antes que las reglas se consultan un diccionario de palabras con sus cortes
+// y una lista de prefijos tras los que se corta siempre. El español trae los
+// suyos; quien transcribe puede añadir más desde un archivo:
+//
+//   # comentario
+//   su-bli-me          palabra con sus sílabas separadas por guiones
+//   prefijo sub-r      prefijo: corta tras "sub" si sigue una r
+//   prefijo trans-     prefijo: corta tras "trans" siga lo que siga
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ErrorBraille;
+
+/// Palabras que ni las reglas ni los prefijos parten bien: sublime y su
+/// familia no llevan el prefijo sub-.
+pub const PALABRAS_ESPANOL: &[&str] = &[
+    "su-bli-me", "su-bli-mes", "su-bli-mar", "su-bli-ma-do", "su-bli-ma-ción",
+    "su-bli-mi-dad",
+];
+
+/// Prefijos que el silabeo fonético partiría mal, con el comienzo que debe
+/// tener el resto de la palabra para que lo sean ("sub-r" sí en subrayar,
+/// no en subir).
+pub const PREFIJOS_ESPANOL: &[&str] = &[
+    "sub-r", "sub-l", "trans-a", "trans-o", "des-a", "des-o", "des-u",
+];
+
+/// Comienzos de palabra que parecen llevar uno de `PREFIJOS_ESPANOL` y no lo
+/// llevan (desolar es de-so-lar).
+const SIN_PREFIJO_ESPANOL: &[&str] = &["desol"];
+
+/// Excepciones que añade quien transcribe; se consultan antes que las de la
+/// tabla.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Excepciones {
+    /// Palabras con sus sílabas separadas por guiones ("su-bli-me").
+    pub palabras: Vec<String>,
+    /// Prefijos con el comienzo que debe seguirles ("sub-r", "trans-").
+    pub prefijos: Vec<String>,
+}
+
+impl Excepciones {
+    /// Interpreta un archivo de excepciones (ver el formato al comienzo del
+    /// módulo). `archivo` solo sirve para los mensajes de error.
+    pub fn desde_texto(archivo: &str, texto: &str) -> Result<Excepciones, ErrorBraille> {
+        let mut excepciones = Excepciones::default();
+        for (n, linea) in texto.lines().enumerate() {
+            let linea = linea.split('#').next().unwrap_or("").trim();
+            if linea.is_empty() {
+                continue;
+            }
+            let error = |detalle: &str| ErrorBraille::ExcepcionesInvalidas {
+                archivo: archivo.to_string(),
+                linea: n + 1,
+                detalle: detalle.to_string(),
+            };
+            let (entrada, prefijo) = match linea.strip_prefix("prefijo") {
+                Some(resto) if resto.starts_with(char::is_whitespace) => (resto.trim(), true),
+                _ => (linea, false),
+            };
+            if entrada.contains(char::is_whitespace) {
+                return Err(error("una sola palabra por línea"));
+            }
+            if !entrada.chars().all(|c| c.is_alphabetic() || c == '-') {
+                return Err(error("solo letras y guiones"));
+            }
+            if prefijo {
+                if !entrada.contains('-') || entrada.starts_with('-') {
+                    return Err(error("el prefijo termina en un guion (sub-r, trans-)"));
+                }
+                excepciones.prefijos.push(entrada.to_lowercase());
+            } else {
+                if entrada.starts_with('-') || entrada.ends_with('-') || entrada.contains("--") {
+                    return Err(error("guion al comienzo, al final o repetido"));
+                }
+                excepciones.palabras.push(entrada.to_lowercase());
+            }
+        }
+        Ok(excepciones)
+    }
+
+    pub fn desde_archivo(ruta: &Path) -> Result<Excepciones, ErrorBraille> {
+        let texto = fs::read_to_string(ruta).map_err(|e| ErrorBraille::Apertura {
+            ruta: ruta.display().to_string(),
+            detalle: e.to_string(),
+        })?;
+        Excepciones::desde_texto(&ruta.display().to_string(), &texto)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.palabras.is_empty() && self.prefijos.is_empty()
+    }
+
+    /// Cortes de la palabra si alguna excepción la cubre; las partes que
+    /// deja un prefijo se silabean con `silabear`.
+    pub fn silabear(&self, palabra: &str, silabear: &dyn Fn(&str) -> Vec<usize>) -> Option<Vec<usize>> {
+        if self.is_empty() {
+            return None;
+        }
+        let palabras: Vec<&str> = self.palabras.iter().map(String::as_str).collect();
+        let prefijos: Vec<&str> = self.prefijos.iter().map(String::as_str).collect();
+        silabear_excepciones(palabra, &palabras, &prefijos, &[], silabear)
+    }
+}
+
+/// Cortes del español: diccionario y prefijos, o `None` si la palabra queda
+/// para las reglas fonéticas.
+pub(crate) fn silabear_espanol(palabra: &str, silabear: &dyn Fn(&str) -> Vec<usize>) -> Option<Vec<usize>> {
+    silabear_excepciones(palabra, PALABRAS_ESPANOL, PREFIJOS_ESPANOL, SIN_PREFIJO_ESPANOL, silabear)
+}
+
+/// Posiciones (en bytes de `palabra`) de inicio de sílaba según el
+/// diccionario o, si no está, según el prefijo más largo con el que empiece.
+/// Un prefijo no se aplica si la palabra empieza por una de `sin_prefijo`
+/// más larga que él, ni si tras él no queda una sílaba.
+fn silabear_excepciones(
+    palabra: &str,
+    palabras: &[&str],
+    prefijos: &[&str],
+    sin_prefijo: &[&str],
+    silabear: &dyn Fn(&str) -> Vec<usize>,
+) -> Option<Vec<usize>> {
+    let original: Vec<char> = palabra.chars().collect();
+    let minusculas: Vec<char> = original.iter()
+        .map(|&c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+    let texto: String = minusculas.iter().collect();
+    // Byte de `palabra` donde empieza cada carácter
+    let bytes: Vec<usize> = original.iter()
+        .scan(0, |b, c| {
+            let inicio = *b;
+            *b += c.len_utf8();
+            Some(inicio)
+        })
+        .collect();
+
+    // Diccionario: cortes en los guiones
+    for entrada in palabras {
+        if entrada.len() - entrada.matches('-').count() != texto.len()
+            || entrada.replace('-', "") != texto
+        {
+            continue;
+        }
+        let mut cortes = vec![0];
+        let mut k = 0;
+        for c in entrada.chars() {
+            if c == '-' {
+                cortes.push(bytes[k]);
+            } else {
+                k += 1;
+            }
+        }
+        return Some(cortes);
+    }
+
+    // Prefijo más largo cuyo contexto coincide
+    let (prefijo, _) = prefijos.iter()
+        .filter_map(|entrada| {
+            let (prefijo, contexto) = entrada.split_once('-')?;
+            let resto = texto.strip_prefix(prefijo)?;
+            let vale = resto.starts_with(contexto)
+                && resto.chars().count() >= 2
+                && resto.chars().any(|c| "aeiouáéíóúü".contains(c))
+                && !sin_prefijo.iter().any(|s| s.len() > prefijo.len() && texto.starts_with(&s.to_lowercase()));
+            vale.then_some((prefijo, entrada.len()))
+        })
+        .max_by_key(|&(prefijo, largo)| (prefijo.len(), largo))?;
+
+    let corte = prefijo.chars().count();
+    let resto: String = minusculas[corte..].iter().collect();
+    let desplazamiento = prefijo.len();
+    let mut cortes: Vec<usize> = silabear(prefijo);
+    if cortes.is_empty() {
+        cortes.push(0);
+    }
+    cortes.extend(silabear(&resto).into_iter().map(|b| b + desplazamiento));
+    // De bytes del texto en minúsculas a bytes de la palabra original
+    let por_caracter = |b: usize| texto[..b].chars().count();
+    Some(cortes.into_iter().map(|b| bytes[por_caracter(b)]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{silabear, silabear_con, SILABEO_ESPANOL};
+
+    /// La palabra con sus sílabas separadas por guiones.
+    fn silabas(palabra: &str) -> String {
+        let cortes = silabear(palabra);
+        let mut resultado = String::new();
+        for (k, c) in palabra.char_indices() {
+            if k > 0 && cortes.contains(&k) {
+                resultado.push('-');
+            }
+            resultado.push(c);
+        }
+        resultado
+    }
+
+    #[test]
+    fn prefijos_clasicos() {
+        assert_eq!(silabas("subrayar"), "sub-ra-yar");
+        assert_eq!(silabas("subrayado"), "sub-ra-ya-do");
+        assert_eq!(silabas("sublunar"), "sub-lu-nar");
+        assert_eq!(silabas("transatlántico"), "trans-at-lán-ti-co");
+        assert_eq!(silabas("desamparo"), "des-am-pa-ro");
+        assert_eq!(silabas("desobedecer"), "des-o-be-de-cer");
+        assert_eq!(silabas("desunir"), "des-u-nir");
+        assert_eq!(silabas("inhumano"), "in-hu-ma-no");
+    }
+
+    #[test]
+    fn sin_prefijo_aunque_lo_parezca() {
+        assert_eq!(silabas("subir"), "su-bir");
+        assert_eq!(silabas("sublime"), "su-bli-me");
+        assert_eq!(silabas("abrir"), "a-brir");
+        assert_eq!(silabas("desear"), "de-se-ar");
+        assert_eq!(silabas("desolar"), "de-so-lar");
+    }
+
+    #[test]
+    fn diccionario_y_mayusculas() {
+        assert_eq!(silabas("sublimación"), "su-bli-ma-ción");
+        assert_eq!(silabas("Subrayar"), "Sub-ra-yar");
+        assert_eq!(silabas("SUBLIME"), "SU-BLI-ME");
+    }
+
+    #[test]
+    fn h_intercalada() {
+        // La h no impide el diptongo: no se parte ante ella
+        assert_eq!(silabas("ahumar"), "ahu-mar");
+        assert_eq!(silabas("prohibir"), "prohi-bir");
+        assert_eq!(silabas("rehusar"), "rehu-sar");
+        // Con hiato, sí
+        assert_eq!(silabas("ahora"), "a-ho-ra");
+        assert_eq!(silabas("búho"), "bú-ho");
+        assert_eq!(silabas("prohíbe"), "pro-hí-be");
+        assert_eq!(silabas("alcohol"), "al-co-hol");
+    }
+
+    #[test]
+    fn excepciones_del_usuario() {
+        let texto = "# pruebas\nca-ta-ra-ta\nprefijo hiper-\n\n";
+        let excepciones = Excepciones::desde_texto("mias.txt", texto).unwrap();
+        assert_eq!(excepciones.palabras, ["ca-ta-ra-ta"]);
+        assert_eq!(excepciones.prefijos, ["hiper-"]);
+
+        let silabear = |p: &str| silabear_con(p, &SILABEO_ESPANOL);
+        assert_eq!(excepciones.silabear("hipermercado", &silabear), Some(vec![0, 2, 5, 8, 10]));
+        assert_eq!(excepciones.silabear("Catarata", &silabear), Some(vec![0, 2, 4, 6]));
+        assert_eq!(excepciones.silabear("casa", &silabear), None);
+    }
+
+    #[test]
+    fn archivo_invalido() {
+        let error = Excepciones::desde_texto("mias.txt", "bien\nmal-\n").unwrap_err();
+        assert_eq!(error.to_string(), "mias.txt, línea 2: guion al comienzo, al final o repetido");
+        assert!(Excepciones::desde_texto("x", "prefijo sub").is_err());
+        assert!(Excepciones::desde_texto("x", "dos palabras").is_err());
+    }
+}
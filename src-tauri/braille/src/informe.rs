@@ -0,0 +1,102 @@
+// ─── Informe de conversión ───────────────────────────────────────────────────
+// Lo que el codificador no pudo representar tal cual: cada carácter sin celda
+// en la tabla, cuántas veces aparece y dónde, para que quien transcribe pueda
+// revisarlo o elegir otra tabla.
+
+use serde::Serialize;
+
+/// Lugar de un carácter en el libro. Todo se cuenta desde 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Posicion {
+    /// Capítulo en orden de lectura (el editor es un único capítulo).
+    pub capitulo: usize,
+    /// Bloque (párrafo, encabezado…) dentro del capítulo.
+    pub bloque: usize,
+    /// Carácter dentro del bloque.
+    pub columna: usize,
+}
+
+/// Carácter que la tabla no sabe codificar.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaracterSinCelda {
+    pub caracter: char,
+    /// Punto de código, "U+20AC", para los que no se ven bien en pantalla.
+    pub codigo: String,
+    pub cantidad: usize,
+    pub posiciones: Vec<Posicion>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Informe {
+    /// En el orden en que aparecen por primera vez.
+    pub sin_celda: Vec<CaracterSinCelda>,
+}
+
+impl Informe {
+    pub fn anotar(&mut self, caracter: char, posicion: Posicion) {
+        match self.sin_celda.iter_mut().find(|s| s.caracter == caracter) {
+            Some(entrada) => {
+                entrada.cantidad += 1;
+                entrada.posiciones.push(posicion);
+            }
+            None => self.sin_celda.push(CaracterSinCelda {
+                caracter,
+                codigo: format!("U+{:04X}", u32::from(caracter)),
+                cantidad: 1,
+                posiciones: vec![posicion],
+            }),
+        }
+    }
+
+    /// Apariciones de caracteres sin celda, contando repeticiones.
+    pub fn total_sin_celda(&self) -> usize {
+        self.sin_celda.iter().map(|s| s.cantidad).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codificar_documento, puntos_a_byte, Documento, OpcionesCodificacion, Posicion,
+        Sustitucion, TablaEspanol, CELDA_SUSTITUCION,
+    };
+
+    fn celdas(texto: &str, desconocidos: Sustitucion) -> Vec<u8> {
+        let opciones = OpcionesCodificacion { desconocidos, ..OpcionesCodificacion::default() };
+        let (celdas, _) = codificar_documento(&Documento::desde_texto(texto), &TablaEspanol, &opciones);
+        celdas.iter().filter(|c| c.salto.is_none()).map(|c| c.byte).collect()
+    }
+
+    #[test]
+    fn anota_cada_caracter_con_su_posicion() {
+        let mut documento = Documento::default();
+        documento.anadir_capitulo(Documento::desde_texto("uno\n50 → o 60 →"));
+        documento.anadir_capitulo(Documento::desde_texto("a♥b"));
+        let (_, informe) =
+            codificar_documento(&documento, &TablaEspanol, &OpcionesCodificacion::default());
+
+        assert_eq!(informe.total_sin_celda(), 3);
+        let flecha = &informe.sin_celda[0];
+        assert_eq!((flecha.caracter, flecha.codigo.as_str(), flecha.cantidad), ('→', "U+2192", 2));
+        assert_eq!(flecha.posiciones[0], Posicion { capitulo: 1, bloque: 2, columna: 4 });
+        assert_eq!(flecha.posiciones[1], Posicion { capitulo: 1, bloque: 2, columna: 11 });
+        let corazon = &informe.sin_celda[1];
+        assert_eq!(corazon.caracter, '♥');
+        assert_eq!(corazon.posiciones, [Posicion { capitulo: 2, bloque: 1, columna: 2 }]);
+    }
+
+    #[test]
+    fn sustituciones() {
+        let a = puntos_a_byte(&[1]);
+        let b = puntos_a_byte(&[1, 2]);
+        let c = puntos_a_byte(&[1, 4]);
+        assert_eq!(celdas("a‹b", Sustitucion::Omitir), [a, b]);
+        assert_eq!(celdas("a‹b", Sustitucion::Marcador), [a, CELDA_SUSTITUCION, b]);
+        assert_eq!(celdas("a‹b", Sustitucion::Transliterar), [a, puntos_a_byte(&[2, 3, 6]), b]);
+        // Una letra sin celda no deja el prefijo de mayúscula suelto
+        assert_eq!(celdas("Ça", Sustitucion::Omitir), [a]);
+        assert_eq!(celdas("Ça", Sustitucion::Transliterar), [puntos_a_byte(&[4, 6]), c, a]);
+    }
+}
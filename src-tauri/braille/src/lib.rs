@@ -0,0 +1,1797 @@
+//! Motor de conversión a braille español (CBE/ONCE), sin dependencias de Tauri.
+//!
+//! El recorrido es siempre el mismo:
+//! EPUB o texto → [`Documento`] → [`codificar_documento`] (celdas con marcas de
+//! sílaba y de bloque) → [`formatear_paginas`] → [`serializar`] (.bin, .brf,
+//! .pef o .txt). Las funciones `convertir_*` lo hacen de una vez.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek};
+use zip::result::ZipError;
+use zip::ZipArchive;
+use serde::{Deserialize, Serialize};
+
+pub mod brf;
+pub mod division;
+pub mod documento;
+mod error;
+pub mod excepciones;
+pub mod informe;
+pub mod normalizacion;
+pub mod notacion;
+pub mod opf;
+pub mod pef;
+pub mod resalte;
+mod silabeo;
+pub mod tabla;
+
+pub use division::Division;
+pub use documento::{Bloque, Documento, TipoBloque};
+pub use error::ErrorBraille;
+pub use excepciones::Excepciones;
+pub use informe::{CaracterSinCelda, Informe, Posicion};
+pub use normalizacion::{normalizar, GuionBlando, Normalizacion};
+pub use opf::Paquete;
+pub use resalte::{Enfasis, EstilosCss, Resalte};
+pub use silabeo::{
+    silabear, silabear_con, silabear_espanol, ReglasSilabeo, SILABEO_ESPANOL,
+    SILABEO_ESPANOL_AMERICA,
+};
+pub use tabla::{
+    elegir_tabla, tabla_para_idioma, Indicador, TablaBraille, TablaEspanol, TablaEspanolAmerica,
+    TablaIdioma, TablaLiblouis,
+};
+
+// ─── Braille: puntos activos → byte ────────────────────────────────────────
+
+/// Byte de celda con los puntos indicados activos (bit 0 = punto 1).
+pub fn puntos_a_byte(puntos: &[u8]) -> u8 {
+    puntos.iter().fold(0u8, |acc, &p| acc | (1 << (p - 1)))
+}
+
+/// Carácter del bloque Unicode braille (U+2800) con los mismos puntos que el byte.
+pub fn byte_a_unicode(byte: u8) -> char {
+    char::from_u32(0x2800 + u32::from(byte & 0x3F)).unwrap_or('\u{2800}')
+}
+
+fn es_mayuscula(c: char) -> bool {
+    c.is_uppercase()
+}
+
+fn a_minuscula(c: char) -> char {
+    match c {
+        '\u{00C1}' => '\u{00E1}',
+        '\u{00C9}' => '\u{00E9}',
+        '\u{00CD}' => '\u{00ED}',
+        '\u{00D3}' => '\u{00F3}',
+        '\u{00DA}' => '\u{00FA}',
+        '\u{00DC}' => '\u{00FC}',
+        '\u{00D1}' => '\u{00F1}',
+        _ => c.to_lowercase().next().unwrap_or(c),
+    }
+}
+
+fn palabra_es_all_caps(palabra: &str) -> bool {
+    let letras: Vec<char> = palabra.chars().filter(|c| c.is_alphabetic()).collect();
+    !letras.is_empty() && letras.iter().all(|c| es_mayuscula(*c))
+}
+
+// Palabras seguidas en mayúsculas a partir de las cuales se escriben como
+// pasaje (CBE: más de tres) en lugar de con un prefijo cada una.
+const MIN_PALABRAS_PASAJE_MAYUSCULAS: usize = 4;
+
+/// Pasajes en mayúsculas: posición de la primera letra de su primera y de su
+/// última palabra. Los números ("1990", el 19 de "COVID19") no cuentan como
+/// palabras ni interrumpen el pasaje; una palabra con minúsculas
+/// ("McDonald", "iPhone") lo termina.
+fn pasajes_mayusculas(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut pasajes = Vec::new();
+    let mut palabras: Vec<usize> = Vec::new();
+    let mut cerrar = |palabras: &mut Vec<usize>| {
+        if palabras.len() >= MIN_PALABRAS_PASAJE_MAYUSCULAS {
+            pasajes.push((palabras[0], palabras[palabras.len() - 1]));
+        }
+        palabras.clear();
+    };
+
+    let mut k = 0;
+    while k < chars.len() {
+        if !chars[k].is_alphanumeric() {
+            k += 1;
+            continue;
+        }
+        let inicio = k;
+        while k < chars.len()
+            && (chars[k].is_alphanumeric() || chars[k] == normalizacion::GUION_BLANDO)
+        {
+            k += 1;
+        }
+        let token = &chars[inicio..k];
+        let Some(primera) = token.iter().position(|c| c.is_alphabetic()) else {
+            continue;
+        };
+        if token.iter().filter(|c| c.is_alphabetic()).all(|&c| es_mayuscula(c)) {
+            palabras.push(inicio + primera);
+        } else {
+            cerrar(&mut palabras);
+        }
+    }
+    cerrar(&mut palabras);
+    pasajes
+}
+
+// ─── Celda braille codificada con metadatos de posición ──────────────────────
+// Necesitamos saber qué celdas son "prefijos" para no separarlos de su carácter
+// y qué celdas son inicio de sílaba para poder insertar el guion de división.
+
+#[derive(Clone, Debug)]
+pub struct CeldaInfo {
+    pub byte: u8,
+    /// true si esta celda es un prefijo (mayúscula o número) que NO puede
+    /// quedar al final de línea separada del carácter al que precede.
+    pub es_prefijo: bool,
+    /// Índice de sílaba al que pertenece esta celda dentro de su palabra.
+    /// None si no forma parte de una palabra (espacio, puntuación, etc.).
+    pub silaba_idx: Option<u32>,
+    /// true si es la primera celda de una nueva sílaba (punto de corte posible).
+    pub inicio_silaba: bool,
+    /// true si es la primera celda de la palabra (útil para el traslado entero).
+    pub inicio_palabra: bool,
+    /// true si es el guion que une las partes de una palabra compuesta
+    /// (franco-alemán): si se parte la línea por él, se repite en la siguiente.
+    pub guion_compuesta: bool,
+    /// Marca de estructura: la celda no se imprime, solo indica al formateador
+    /// dónde empieza un párrafo o una línea nueva.
+    pub salto: Option<Salto>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Salto {
+    /// Nuevo bloque (párrafo, título…): línea nueva y la disposición que
+    /// corresponda a su tipo (sangría, centrado, líneas en blanco).
+    Bloque(TipoBloque),
+    /// Salto de línea dentro del mismo párrafo (U+2028, `<br>`).
+    Linea,
+}
+
+// Separador de línea Unicode: salto forzado que no abre párrafo.
+pub const SEPARADOR_LINEA: char = '\u{2028}';
+
+impl CeldaInfo {
+    pub fn salto(salto: Salto) -> Self {
+        CeldaInfo {
+            byte: 0x00,
+            es_prefijo: false,
+            silaba_idx: None,
+            inicio_silaba: false,
+            inicio_palabra: false,
+            guion_compuesta: false,
+            salto: Some(salto),
+        }
+    }
+}
+
+// ─── Caracteres sin celda en la tabla ────────────────────────────────────────
+
+/// Celda que ocupa el lugar de un carácter sin correspondencia (puntos 1-2-3-4-5-6).
+pub const CELDA_SUSTITUCION: u8 = 0b0011_1111;
+
+/// Qué hacer con un carácter que la tabla no sabe codificar. En todos los
+/// casos queda anotado en el informe de la conversión.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sustitucion {
+    /// Se omite.
+    #[default]
+    Omitir,
+    /// Se escribe `CELDA_SUSTITUCION` en su lugar.
+    Marcador,
+    /// Se escribe un equivalente que la tabla sí conozca (« → ", … → ...,
+    /// ç → c); si no lo hay, se omite.
+    Transliterar,
+}
+
+/// El punto entre cifras ("1.809"). La regla 9.2 de la CBE dice que el punto
+/// interrumpe el número y se repite el prefijo, pero su propio ejemplo escribe
+/// "1.809" en 6 celdas, como separador de millares; se puede elegir.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PuntoEnNumero {
+    /// Según el texto de la regla: ⠼⠁⠄⠼⠓⠚⠊ (7 celdas).
+    #[default]
+    RepitePrefijo,
+    /// Según el ejemplo: ⠼⠁⠄⠓⠚⠊ (6 celdas).
+    Millares,
+}
+
+/// Opciones del codificador elegidas en tiempo de ejecución.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpcionesCodificacion {
+    pub normalizacion: Normalizacion,
+    pub desconocidos: Sustitucion,
+    pub punto_en_numero: PuntoEnNumero,
+    /// Resaltes del libro que llevan indicador.
+    pub enfasis: Enfasis,
+    /// Excepciones de silabeo propias, antes que las de la tabla.
+    pub excepciones: Excepciones,
+    /// Qué fronteras de sílaba sirven para partir una palabra a final de línea.
+    pub division: Division,
+}
+
+/// Equivalente aproximado, con caracteres más comunes, de un signo o de una
+/// letra (en minúscula) que falte en la tabla.
+fn transliterar(c: char) -> Option<&'static str> {
+    Some(match c {
+        '«' | '»' | '„' | '‟' | '‹' | '›' => "\"",
+        '‘' | '’' | '‚' | '‛' | '′' | '`' | '´' => "'",
+        '—' | '–' | '‐' | '‑' | '‒' | '―' | '−' | '•' => "-",
+        '…' => "...",
+        '\t' => " ",
+        '×' => "x",
+        '€' => "EUR",
+        '£' => "GBP",
+        'ª' => "a",
+        'º' => "o",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'þ' => "th",
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ĺ' | 'ľ' | 'ł' => "l",
+        'ń' | 'ň' => "n",
+        'ò' | 'ô' | 'ö' | 'õ' | 'ø' | 'ō' | 'ő' => "o",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'ù' | 'û' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+/// Celdas para una letra sin correspondencia, según la opción elegida.
+fn sustituir_letra(c: char, tabla: &dyn TablaBraille, sustitucion: Sustitucion) -> Option<Vec<u8>> {
+    match sustitucion {
+        Sustitucion::Omitir => None,
+        Sustitucion::Marcador => Some(vec![CELDA_SUSTITUCION]),
+        Sustitucion::Transliterar => transliterar(c)?
+            .chars()
+            .map(|l| tabla.letra(l))
+            .collect::<Option<Vec<_>>>()
+            .map(|celdas| celdas.concat()),
+    }
+}
+
+// ─── Codificador de texto → Vec<CeldaInfo> ───────────────────────────────────
+
+pub fn codificar_texto(texto: &str, tabla: &dyn TablaBraille) -> Vec<CeldaInfo> {
+    codificar(texto, tabla, &OpcionesCodificacion::default(), false).0
+}
+
+/// Normaliza y codifica el texto; devuelve además los caracteres sin celda
+/// en la tabla, con su índice (en caracteres del texto normalizado, desde 0).
+/// `en_titulo` indica que el texto es un encabezado, donde un número romano
+/// al comienzo se reconoce como tal.
+fn codificar(
+    texto: &str,
+    tabla: &dyn TablaBraille,
+    opciones: &OpcionesCodificacion,
+    en_titulo: bool,
+) -> (Vec<CeldaInfo>, Vec<(usize, char)>) {
+    let mut resultado: Vec<CeldaInfo> = Vec::new();
+    let mut sin_celda: Vec<(usize, char)> = Vec::new();
+    let mut modo_numerico = false;
+    let normalizado: Vec<char> = normalizar(texto, &opciones.normalizacion).chars().collect();
+    let (chars, tramos) = resalte::separar(&normalizado, &opciones.enfasis);
+    let n = chars.len();
+    let mut i = 0;
+    // Tramo resaltado en curso, si ya se escribió su indicador; el de una
+    // palabra que empieza por letra espera a que la palabra lo coloque
+    let mut tramo = 0;
+    let mut tramo_abierto = false;
+    let mut resalte_pendiente: Option<Vec<u8>> = None;
+    let pasajes = pasajes_mayusculas(&chars);
+    let mut pasaje = 0;
+
+    // Una entrada de la tabla puede ocupar varias celdas: las marcas de
+    // sílaba y de palabra van en la primera.
+    let push = |resultado: &mut Vec<CeldaInfo>, celdas: &[u8], es_pref: bool,
+                sil: Option<u32>, inicio_s: bool, inicio_p: bool| {
+        for (k, &byte) in celdas.iter().enumerate() {
+            resultado.push(CeldaInfo {
+                byte,
+                es_prefijo: es_pref,
+                silaba_idx: sil,
+                inicio_silaba: inicio_s && k == 0,
+                inicio_palabra: inicio_p && k == 0,
+                guion_compuesta: false,
+                salto: None,
+            });
+        }
+    };
+    // Ordinales, fracciones e índices se escriben como una unidad: ninguna
+    // de sus celdas se separa de la siguiente
+    let push_unidad = |resultado: &mut Vec<CeldaInfo>, celdas: &[u8]| {
+        for (k, &byte) in celdas.iter().enumerate() {
+            push(resultado, &[byte], k + 1 < celdas.len(), None, false, false);
+        }
+    };
+    let prefijo_mayuscula = tabla.indicador(Indicador::Mayuscula);
+    let prefijo_numero = tabla.indicador(Indicador::Numero);
+    let interruptor = tabla.indicador(Indicador::LetraTrasNumero);
+
+    // Una letra cuya celda es también la de una cifra no puede ir pegada a
+    // un número sin el interruptor (en CBE, a–j)
+    let celdas_cifras: Vec<u8> = ('0'..='9')
+        .filter_map(|d| tabla.digito(d))
+        .filter_map(|celdas| celdas.first().copied())
+        .collect();
+    let parece_cifra = |celdas: &[u8]| celdas.first().is_some_and(|c| celdas_cifras.contains(c));
+
+    while i < n {
+        let c = chars[i];
+
+        // ── Resalte: apertura y cierre de los tramos ──
+        if tramo_abierto && i >= tramos[tramo].fin {
+            if tramos[tramo].pasaje {
+                push(&mut resultado, &tabla.indicador(Indicador::ResalteCierre), false, None, false, false);
+            }
+            tramo_abierto = false;
+            tramo += 1;
+        }
+        if !tramo_abierto && tramos.get(tramo).is_some_and(|t| i >= t.inicio) {
+            let indicador = if tramos[tramo].pasaje {
+                Indicador::ResalteApertura
+            } else {
+                Indicador::ResaltePalabra
+            };
+            if c.is_alphabetic() {
+                resalte_pendiente = Some(tabla.indicador(indicador));
+            } else {
+                push(&mut resultado, &tabla.indicador(indicador), true, None, false, false);
+            }
+            tramo_abierto = true;
+        }
+
+        // ── Saltos de párrafo: una racha de \n / \r es un único salto ──
+        if c == '\n' || c == '\r' {
+            while i < n && (chars[i] == '\n' || chars[i] == '\r') {
+                i += 1;
+            }
+            resultado.push(CeldaInfo::salto(Salto::Bloque(TipoBloque::Parrafo)));
+            modo_numerico = false;
+            continue;
+        }
+
+        if c == SEPARADOR_LINEA {
+            resultado.push(CeldaInfo::salto(Salto::Linea));
+            modo_numerico = false;
+            i += 1;
+            continue;
+        }
+
+        // Guion blando fuera de una palabra: no marca ningún corte
+        if c == normalizacion::GUION_BLANDO {
+            i += 1;
+            continue;
+        }
+
+        // ── Espacios ──
+        if c == ' ' {
+            push(&mut resultado, &[puntos_a_byte(&[])], false, None, false, false);
+            modo_numerico = false;
+            i += 1;
+            continue;
+        }
+
+        // ── Exponentes y subíndices ("x²", "H₂O") ──
+        if let Some((largo, celdas)) = notacion::indice(&chars[i..], tabla) {
+            push_unidad(&mut resultado, &celdas);
+            modo_numerico = false;
+            i += largo;
+            continue;
+        }
+
+        // ── Ordinales ("1.º", "2.ª") y fracciones ("3/4"; no en "12/05/2020") ──
+        if tabla.digito(c).is_some() && !modo_numerico {
+            let tras_barra = i > 0 && matches!(chars[i - 1], '/' | '\u{2044}');
+            let notacion = notacion::ordinal(&chars[i..], tabla).or_else(|| {
+                if tras_barra { None } else { notacion::fraccion(&chars[i..], tabla) }
+            });
+            if let Some((largo, celdas)) = notacion {
+                push_unidad(&mut resultado, &celdas);
+                i += largo;
+                continue;
+            }
+        }
+
+        // ── Dígitos ──
+        if let Some(celdas) = tabla.digito(c) {
+            if !modo_numerico {
+                push(&mut resultado, &prefijo_numero, true, None, false, false);
+                modo_numerico = true;
+            }
+            push(&mut resultado, &celdas, false, None, false, false);
+            i += 1;
+            continue;
+        }
+
+        // ── Letras ──
+        // Procesamos la palabra COMPLETA de una vez al encontrar su primer carácter,
+        // y avanzamos i hasta el final. Esto evita recalcular offsets letra a letra.
+        if c.is_alphabetic() {
+            let tras_numero = modo_numerico;
+            modo_numerico = false;
+
+            // Solo procesamos si estamos en el inicio de la palabra
+            let en_inicio_palabra = i == 0 || !chars[i - 1].is_alphabetic();
+            if !en_inicio_palabra {
+                // No debería ocurrir: siempre saltamos al fin_pal tras procesar
+                i += 1;
+                continue;
+            }
+
+            // Fin de la palabra; los guiones blandos conservados entre letras
+            // son parte de ella
+            let fin_pal = {
+                let mut j = i;
+                while j < n && (chars[j].is_alphabetic()
+                    || (chars[j] == normalizacion::GUION_BLANDO
+                        && chars.get(j + 1).is_some_and(|c| c.is_alphabetic())))
+                {
+                    j += 1;
+                }
+                j
+            };
+            // Posición en el texto de cada letra de la palabra
+            let indices: Vec<usize> = (i..fin_pal)
+                .filter(|&j| chars[j] != normalizacion::GUION_BLANDO)
+                .collect();
+            let num_chars = indices.len();
+            let palabra: String = indices.iter().map(|&j| chars[j]).collect();
+
+            // Silabear y construir mapas por carácter. Si el libro trae guiones
+            // blandos, solo se parte por ellos.
+            let silaba_inicios = if num_chars < fin_pal - i {
+                let mut cortes = vec![0];
+                let mut byte_off = 0usize;
+                for &j in &indices {
+                    if j > i && chars[j - 1] == normalizacion::GUION_BLANDO {
+                        cortes.push(byte_off);
+                    }
+                    byte_off += chars[j].len_utf8();
+                }
+                cortes
+            } else {
+                opciones.excepciones.silabear(&palabra, &|p| tabla.silabear(p))
+                    .unwrap_or_else(|| tabla.silabear(&palabra))
+            };
+
+            let mut char_silaba   = vec![0u32;  num_chars];
+            let mut char_inicio_s = vec![false; num_chars];
+            {
+                let mut sil_idx  = 0u32;
+                let mut byte_off = 0usize;
+                for (k, ch) in palabra.chars().enumerate() {
+                    // El offset 0 es siempre inicio de primera sílaba, no marcamos
+                    // inicio_silaba[0] porque no hay "corte antes del primer char".
+                    if k > 0 && silaba_inicios.contains(&byte_off) {
+                        sil_idx += 1;
+                        char_inicio_s[k] = true;
+                    }
+                    char_silaba[k] = sil_idx;
+                    byte_off += ch.len_utf8();
+                }
+            }
+
+            let todo_mayusculas = palabra_es_all_caps(&palabra);
+            let letras: Vec<char> = palabra.chars().collect();
+            let minusculas: Vec<char> = letras.iter().map(|&c| a_minuscula(c)).collect();
+
+            if let Some(celdas) = resalte_pendiente.take() {
+                push(&mut resultado, &celdas, true, Some(0), false, true);
+            }
+
+            // Pasaje en mayúsculas: su indicador ante la primera palabra, el
+            // de cierre ante la última y ningún prefijo en las demás
+            while pasajes.get(pasaje).is_some_and(|&(_, ultima)| ultima < i) {
+                pasaje += 1;
+            }
+            let en_pasaje = todo_mayusculas
+                && pasajes.get(pasaje).is_some_and(|&(primera, _)| primera <= i);
+            if en_pasaje {
+                let (primera, ultima) = pasajes[pasaje];
+                if i == primera {
+                    push(&mut resultado, &tabla.indicador(Indicador::MayusculasApertura),
+                         true, Some(0), false, true);
+                }
+                if i == ultima {
+                    push(&mut resultado, &tabla.indicador(Indicador::MayusculasCierre),
+                         true, Some(0), false, true);
+                }
+            }
+
+            // Número romano de varias letras ("capítulo XIV"): doble prefijo
+            // de mayúscula y nunca se parte
+            let romano = todo_mayusculas && !en_pasaje && num_chars > 1
+                && notacion::es_romano_en_contexto(&palabra, &chars[..i], &chars[fin_pal..], en_titulo);
+            if romano {
+                push(&mut resultado, &prefijo_mayuscula, true, Some(0), false, true);
+                char_silaba.fill(0);
+                char_inicio_s.fill(false);
+            }
+            opciones.division.filtrar(&letras, &mut char_inicio_s, &chars[..i], &chars[fin_pal..], en_pasaje);
+
+            // ALL CAPS → prefijo único antes de la primera letra
+            if todo_mayusculas && !en_pasaje {
+                push(&mut resultado,
+                     &prefijo_mayuscula,
+                     true,               // es_prefijo → no separar del siguiente
+                     Some(char_silaba[0]),
+                     false,              // el prefijo no es "inicio_silaba" por sí solo
+                     !romano);
+            }
+
+            // Palabra con entrada propia en la tabla (`word` de liblouis): va
+            // entera, sin puntos de corte
+            let completa: String = minusculas.iter().collect();
+            if let Some(celdas) = tabla.palabra(&completa) {
+                if !todo_mayusculas && es_mayuscula(letras[0]) {
+                    push(&mut resultado, &prefijo_mayuscula, true, Some(0), false, true);
+                } else if !todo_mayusculas && tras_numero && parece_cifra(&celdas) {
+                    push(&mut resultado, &interruptor, true, Some(0), false, true);
+                }
+                push(&mut resultado, &celdas, false, Some(0), false, true);
+                i = fin_pal;
+                continue;
+            }
+
+            // Letra a letra; una secuencia de la tabla (`always`) ocupa el
+            // lugar de varias y toma la sílaba de la primera
+            let mut k = 0;
+            while k < num_chars {
+                let (largo, celdas) = match tabla.secuencia(&minusculas[k..]) {
+                    Some((largo, celdas)) => (largo, Some(celdas)),
+                    None => (1, tabla.letra(minusculas[k])),
+                };
+                let celdas = celdas.or_else(|| {
+                    sin_celda.push((indices[k], letras[k]));
+                    sustituir_letra(minusculas[k], tabla, opciones.desconocidos)
+                });
+                let sil      = char_silaba[k];
+                let is_s     = char_inicio_s[k];
+                let es_ini_p = k == 0;
+
+                if !todo_mayusculas && es_mayuscula(letras[k]) {
+                    // Prefijo individual de mayúscula, solo si la letra se escribe
+                    if let Some(celdas) = celdas {
+                        push(&mut resultado,
+                             &prefijo_mayuscula,
+                             true, Some(sil), is_s, es_ini_p);
+                        push(&mut resultado, &celdas, false,
+                             Some(sil), false, false);
+                    }
+                } else if let Some(celdas) = celdas {
+                    if k == 0 && !todo_mayusculas && tras_numero && parece_cifra(&celdas) {
+                        // "3a": el interruptor abre la palabra y va unido a la letra
+                        push(&mut resultado, &interruptor, true, Some(sil), false, true);
+                        push(&mut resultado, &celdas, false, Some(sil), is_s, false);
+                    } else {
+                        push(&mut resultado, &celdas, false,
+                             Some(sil), is_s, es_ini_p);
+                    }
+                }
+                k += largo;
+            }
+
+            i = fin_pal; // saltar la palabra entera de una vez
+            continue;
+        }
+
+
+        // ── Fracciones de un carácter (½, ¾) ──
+        if let Some(celdas) = notacion::fraccion_caracter(c, tabla) {
+            push_unidad(&mut resultado, &celdas);
+            modo_numerico = false;
+            i += 1;
+            continue;
+        }
+
+        // ── Secuencias de signos de la tabla ("..." → una sola entrada) ──
+        if let Some((largo, celdas)) = tabla.secuencia(&chars[i..]) {
+            push(&mut resultado, &celdas, false, None, false, false);
+            modo_numerico = false;
+            i += largo;
+            continue;
+        }
+
+        // ── Puntuación ──
+        // El apóstrofo tipográfico (’) es el mismo carácter que la comilla
+        // simple de cierre: entre dos letras se escribe como apóstrofo.
+        let entre_letras = i > 0 && chars[i - 1].is_alphabetic()
+            && chars.get(i + 1).is_some_and(|s| s.is_alphabetic());
+        let signo = if c == '\u{2019}' && entre_letras { '\'' } else { c };
+        if let Some(celdas) = tabla.puntuacion(signo) {
+            // Entre dos cifras, la coma decimal ("3,14") sigue dentro del
+            // número, y el punto también si se escribe como separador de
+            // millares (ver `PuntoEnNumero`). Cualquier otro signo lo termina
+            // y la cifra siguiente vuelve a llevar el prefijo ("3-4", "10:30").
+            let sigue_cifra = chars.get(i + 1).is_some_and(|&s| tabla.digito(s).is_some());
+            modo_numerico = modo_numerico && sigue_cifra && match c {
+                ',' => true,
+                '.' => opciones.punto_en_numero == PuntoEnNumero::Millares,
+                _ => false,
+            };
+            push(&mut resultado, &celdas, false, None, false, false);
+            if entre_letras && division::es_guion(c) {
+                if let Some(ultima) = resultado.last_mut() {
+                    ultima.guion_compuesta = true;
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        // ── Sin correspondencia en la tabla ──
+        sin_celda.push((i, c));
+        modo_numerico = false;
+        match opciones.desconocidos {
+            Sustitucion::Omitir => {}
+            Sustitucion::Marcador => {
+                push(&mut resultado, &[CELDA_SUSTITUCION], false, None, false, false);
+            }
+            Sustitucion::Transliterar => {
+                if let Some(equivalente) = transliterar(c) {
+                    // Lo que el equivalente tampoco tenga en la tabla se omite
+                    let (celdas, _) = codificar(equivalente, tabla, &OpcionesCodificacion::default(), false);
+                    resultado.extend(celdas);
+                }
+            }
+        }
+        i += 1;
+    }
+    if tramo_abierto && tramos[tramo].pasaje {
+        push(&mut resultado, &tabla.indicador(Indicador::ResalteCierre), false, None, false, false);
+    }
+
+    (resultado, sin_celda)
+}
+
+/// Codifica el documento bloque a bloque; cada bloque va precedido de la
+/// marca con su tipo para que el formateador lo disponga. El informe recoge
+/// los caracteres sin celda en la tabla.
+pub fn codificar_documento(
+    documento: &Documento,
+    tabla: &dyn TablaBraille,
+    opciones: &OpcionesCodificacion,
+) -> (Vec<CeldaInfo>, Informe) {
+    let mut resultado = Vec::new();
+    let mut informe = Informe::default();
+    for (indice, bloque) in documento.bloques.iter().enumerate() {
+        resultado.push(CeldaInfo::salto(Salto::Bloque(bloque.tipo)));
+        let en_titulo = matches!(bloque.tipo, TipoBloque::Encabezado(_));
+        let (celdas, sin_celda) = codificar(&bloque.texto, tabla, opciones, en_titulo);
+        resultado.extend(celdas);
+
+        let (capitulo, numero) = documento.posicion_bloque(indice);
+        for (columna, c) in sin_celda {
+            informe.anotar(c, Posicion { capitulo, bloque: numero, columna: columna + 1 });
+        }
+    }
+    (resultado, informe)
+}
+
+// ─── Formateador con silabación y reglas ONCE ─────────────────────────────────
+
+const CELDAS_POR_LINEA: usize = 30;
+const LINEAS_POR_PAGINA: usize = 25;
+const CTRL_SALTO_LINEA: u8 = 0xFF;
+const CTRL_SALTO_PAGINA: u8 = 0xFE;
+
+// Un prefijo y su carácter deben caber juntos en una línea.
+const MIN_CELDAS_POR_LINEA: usize = 2;
+
+// Celdas en blanco que separan el texto del número de página en su línea.
+const SEPARACION_NUMERO_PAGINA: usize = 3;
+
+// Sangría de primera línea de párrafo (celdas en blanco).
+const SANGRIA_PARRAFO: usize = 2;
+
+/// Línea en la que se coloca el número de página braille.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumeracionPagina {
+    /// Primera línea, alineado a la derecha (práctica habitual ONCE).
+    #[default]
+    Superior,
+    /// Última línea, alineado a la derecha.
+    Inferior,
+    /// Sin número de página.
+    Ninguna,
+}
+
+/// Parámetros de maquetación elegidos en tiempo de ejecución.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Maquetacion {
+    /// Celdas por línea del embosador/papel (25, 30, 32, 40, 42…).
+    pub celdas_por_linea: usize,
+    /// Líneas por página braille.
+    pub lineas_por_pagina: usize,
+    pub numeracion: NumeracionPagina,
+    /// Celdas en blanco al comienzo de la primera línea de cada párrafo.
+    pub sangria: usize,
+    /// Dejar una línea en blanco entre párrafos.
+    pub linea_entre_parrafos: bool,
+    /// Encabezados de este nivel o superior (h1…hN) son títulos de capítulo.
+    pub nivel_capitulo: u8,
+    /// Centrar los títulos de capítulo en la línea.
+    pub centrar_titulos: bool,
+    /// Líneas en blanco antes de un encabezado (salvo a comienzo de página).
+    pub lineas_antes_titulo: usize,
+    /// Líneas en blanco tras un encabezado.
+    pub lineas_despues_titulo: usize,
+    /// Cada título de capítulo empieza en una página braille nueva.
+    pub capitulo_en_pagina_nueva: bool,
+}
+
+impl Default for Maquetacion {
+    fn default() -> Self {
+        Maquetacion {
+            celdas_por_linea: CELDAS_POR_LINEA,
+            lineas_por_pagina: LINEAS_POR_PAGINA,
+            numeracion: NumeracionPagina::default(),
+            sangria: SANGRIA_PARRAFO,
+            linea_entre_parrafos: false,
+            nivel_capitulo: 1,
+            centrar_titulos: true,
+            lineas_antes_titulo: 1,
+            lineas_despues_titulo: 1,
+            capitulo_en_pagina_nueva: false,
+        }
+    }
+}
+
+impl Maquetacion {
+    pub fn validar(&self) -> Result<(), ErrorBraille> {
+        if self.celdas_por_linea < MIN_CELDAS_POR_LINEA {
+            return Err(ErrorBraille::Maquetacion(format!(
+                "El ancho de línea debe ser de al menos {} celdas", MIN_CELDAS_POR_LINEA)));
+        }
+        // Con numeración, la línea del número puede quedarse sin sitio para
+        // texto: hace falta al menos otra línea por página.
+        let min_lineas = if self.numeracion == NumeracionPagina::Ninguna { 1 } else { 2 };
+        if self.lineas_por_pagina < min_lineas {
+            return Err(ErrorBraille::Maquetacion(format!(
+                "La página debe tener al menos {} líneas", min_lineas)));
+        }
+        if self.sangria >= self.celdas_por_linea {
+            return Err(ErrorBraille::Maquetacion(
+                "La sangría debe ser menor que el ancho de línea".to_string()));
+        }
+        if self.nivel_capitulo > 6 {
+            return Err(ErrorBraille::Maquetacion(
+                "El nivel de capítulo va de 0 (ninguno) a 6".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Índice, dentro de la página, de la línea que lleva el número.
+    fn linea_numerada(&self) -> Option<usize> {
+        match self.numeracion {
+            NumeracionPagina::Superior => Some(0),
+            NumeracionPagina::Inferior => Some(self.lineas_por_pagina - 1),
+            NumeracionPagina::Ninguna => None,
+        }
+    }
+}
+
+/// Página braille maquetada: líneas rellenas hasta el ancho configurado,
+/// con el número de página ya colocado si corresponde.
+#[derive(Clone, Debug)]
+pub struct Pagina {
+    pub numero: u32,
+    pub lineas: Vec<Vec<u8>>,
+}
+
+/// Número de página en braille: prefijo numérico + dígitos.
+fn celdas_numero_pagina(numero: u32, tabla: &dyn TablaBraille) -> Vec<u8> {
+    let mut celdas = tabla.indicador(Indicador::Numero);
+    celdas.extend(numero.to_string().chars()
+        .filter_map(|c| tabla.digito(c))
+        .flatten());
+    celdas
+}
+
+// ─── Maquetador: líneas y páginas en curso ───────────────────────────────────
+// Lleva la cuenta de la línea dentro de la página para saber cuánto texto
+// cabe en cada línea: la que lleva el número de página tiene menos sitio.
+
+struct Maquetador<'a> {
+    config: &'a Maquetacion,
+    tabla: &'a dyn TablaBraille,
+    /// Celdas del guion de división de la tabla.
+    guion: Vec<u8>,
+    paginas: Vec<Pagina>,
+    /// Líneas ya cerradas de la página en curso.
+    lineas: Vec<Vec<u8>>,
+    /// Línea en curso, sin relleno.
+    linea: Vec<u8>,
+    /// Centrar cada línea al cerrarla (títulos).
+    centrar: bool,
+}
+
+impl<'a> Maquetador<'a> {
+    fn new(config: &'a Maquetacion, tabla: &'a dyn TablaBraille) -> Self {
+        Maquetador {
+            config,
+            tabla,
+            guion: tabla.indicador(Indicador::GuionDivision),
+            paginas: Vec::new(),
+            lineas: Vec::new(),
+            linea: Vec::new(),
+            centrar: false,
+        }
+    }
+
+    fn numero_pagina(&self) -> u32 {
+        self.paginas.len() as u32 + 1
+    }
+
+    fn lleva_numero(&self) -> bool {
+        self.config.linea_numerada() == Some(self.lineas.len())
+    }
+
+    /// Celdas de texto que admite la línea en curso. Puede ser 0 si el ancho
+    /// no deja sitio al texto junto al número de página.
+    fn capacidad(&self) -> usize {
+        let ancho = self.config.celdas_por_linea;
+        if self.lleva_numero() {
+            let reservado = celdas_numero_pagina(self.numero_pagina(), self.tabla).len()
+                + SEPARACION_NUMERO_PAGINA;
+            ancho.saturating_sub(reservado)
+        } else {
+            ancho
+        }
+    }
+
+    fn restante(&self) -> usize {
+        self.capacidad().saturating_sub(self.linea.len())
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.linea.push(byte);
+    }
+
+    /// Primera línea de párrafo: `celdas` en blanco al comienzo.
+    fn sangrar(&mut self, celdas: usize) {
+        while self.restante() <= celdas {
+            self.cerrar_linea();
+        }
+        self.linea.resize(self.linea.len() + celdas, 0x00);
+    }
+
+    /// Línea vacía de separación; nunca al comienzo de una página.
+    fn linea_en_blanco(&mut self) {
+        if !self.linea.is_empty() {
+            self.cerrar_linea();
+        }
+        if !self.lineas.is_empty() {
+            self.cerrar_linea();
+        }
+    }
+
+    /// Termina la página en curso si ya tiene algo escrito.
+    fn saltar_pagina(&mut self) {
+        if !self.linea.is_empty() {
+            self.cerrar_linea();
+        }
+        if !self.lineas.is_empty() {
+            self.cerrar_pagina();
+        }
+    }
+
+    /// Prepara la disposición de un bloque justo antes de su primera celda
+    /// visible: página nueva, líneas en blanco, centrado y sangría.
+    fn abrir_bloque(&mut self, tipo: TipoBloque, anterior: Option<TipoBloque>) {
+        let config = self.config;
+        self.centrar = false;
+
+        let mut blancas = 0;
+        if let Some(anterior) = anterior {
+            if matches!(anterior, TipoBloque::Encabezado(_)) {
+                blancas = config.lineas_despues_titulo;
+            }
+            if config.linea_entre_parrafos {
+                blancas = blancas.max(1);
+            }
+        }
+
+        let mut sangria = config.sangria;
+        let mut centrar = false;
+        if let TipoBloque::Encabezado(nivel) = tipo {
+            let capitulo = nivel <= config.nivel_capitulo;
+            if capitulo && config.capitulo_en_pagina_nueva {
+                self.saltar_pagina();
+            }
+            blancas = blancas.max(config.lineas_antes_titulo);
+            sangria = 0;
+            centrar = capitulo && config.centrar_titulos;
+        }
+
+        for _ in 0..blancas {
+            self.linea_en_blanco();
+        }
+        self.centrar = centrar;
+        if sangria > 0 {
+            self.sangrar(sangria);
+        }
+    }
+
+    /// Rellena la línea en curso hasta el ancho (colocando el número de
+    /// página si le toca) y la cierra; al completar la página, la cierra.
+    fn cerrar_linea(&mut self) {
+        let ancho = self.config.celdas_por_linea;
+        let mut linea = std::mem::take(&mut self.linea);
+        if self.centrar {
+            while linea.last() == Some(&0x00) {
+                linea.pop();
+            }
+            let margen = self.capacidad().saturating_sub(linea.len()) / 2;
+            linea.splice(0..0, std::iter::repeat_n(0x00, margen));
+        }
+        if self.lleva_numero() {
+            let numero = celdas_numero_pagina(self.numero_pagina(), self.tabla);
+            linea.resize(ancho.saturating_sub(numero.len()), 0x00);
+            linea.extend(numero);
+            linea.truncate(ancho);
+        } else {
+            linea.resize(ancho, 0x00);
+        }
+        self.lineas.push(linea);
+
+        if self.lineas.len() >= self.config.lineas_por_pagina {
+            self.cerrar_pagina();
+        }
+    }
+
+    fn cerrar_pagina(&mut self) {
+        // Con numeración inferior, completar la página para que el número
+        // llegue a la última línea.
+        if self.config.numeracion == NumeracionPagina::Inferior {
+            while self.lineas.len() < self.config.lineas_por_pagina {
+                self.cerrar_linea();
+                if self.lineas.is_empty() {
+                    return; // cerrar_linea ya cerró la página
+                }
+            }
+        }
+        let numero = self.numero_pagina();
+        let lineas = std::mem::take(&mut self.lineas);
+        self.paginas.push(Pagina { numero, lineas });
+    }
+
+    fn terminar(mut self) -> Vec<Pagina> {
+        if !self.linea.is_empty() {
+            self.cerrar_linea();
+        }
+        if !self.lineas.is_empty() {
+            self.cerrar_pagina();
+        }
+        self.paginas
+    }
+}
+
+/// Formatea las celdas respetando:
+/// 1. Máximo `celdas_por_linea` por línea (menos en la línea del número de página).
+/// 2. Nunca separar un prefijo del carácter al que modifica.
+/// 3. División silábica con guion braille al final de línea.
+/// 4. Si no cabe ninguna sílaba completa, trasladar la palabra entera; una
+///    compuesta partida por su guion lo repite al comienzo de la línea nueva.
+/// 5. `lineas_por_pagina` líneas por página, numeradas según la configuración.
+/// 6. Cada bloque empieza en línea nueva: los párrafos con sangría y, si se
+///    pide, tras una línea en blanco; los títulos de capítulo centrados, entre
+///    líneas en blanco y, si se pide, en página nueva.
+///
+/// Cada formato de salida decide cómo serializar las páginas.
+pub fn formatear_paginas(
+    celdas: &[CeldaInfo],
+    maquetacion: &Maquetacion,
+    tabla: &dyn TablaBraille,
+) -> Vec<Pagina> {
+    let mut m = Maquetador::new(maquetacion, tabla);
+    let n = celdas.len();
+    let mut i = 0usize;
+    // La disposición de cada bloque se aplica al llegar su primera celda
+    // visible, así los bloques vacíos no dejan rastro. El texto sin marca
+    // inicial empieza como párrafo.
+    let mut bloque_pendiente: Option<TipoBloque> = Some(TipoBloque::Parrafo);
+    let mut bloque_anterior: Option<TipoBloque> = None;
+
+    while i < n {
+        let celda = &celdas[i];
+
+        // ── Saltos de párrafo y de línea ──
+        if let Some(salto) = celda.salto {
+            match salto {
+                Salto::Bloque(tipo) => {
+                    if bloque_pendiente.is_none() && !m.linea.is_empty() {
+                        m.cerrar_linea();
+                    }
+                    bloque_pendiente = Some(tipo);
+                }
+                Salto::Linea => {
+                    if bloque_pendiente.is_none() {
+                        m.cerrar_linea();
+                    }
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        // ── Espacio ──
+        if celda.byte == 0x00 && !celda.es_prefijo {
+            // Un espacio a comienzo de línea o de párrafo no se imprime
+            if m.linea.is_empty() || bloque_pendiente.is_some() {
+                i += 1;
+                continue;
+            }
+            // Si la línea está llena, el espacio es el salto: se cierra y no
+            // pasa al comienzo de la siguiente
+            if m.restante() == 0 {
+                m.cerrar_linea();
+                i += 1;
+                continue;
+            }
+            m.push(0x00);
+            i += 1;
+            continue;
+        }
+
+        if let Some(tipo) = bloque_pendiente.take() {
+            m.abrir_bloque(tipo, bloque_anterior);
+            bloque_anterior = Some(tipo);
+        }
+
+        // ── Inicio de palabra alfabética ──
+        if celda.inicio_palabra && celda.silaba_idx.is_some() {
+            // Recopilar todas las celdas de esta palabra
+            let inicio_pal = i;
+            let mut fin_pal = i;
+            while fin_pal < n && celdas[fin_pal].silaba_idx.is_some() {
+                fin_pal += 1;
+            }
+            // celdas[inicio_pal..fin_pal] es la palabra completa
+
+            colocar_palabra(&celdas[inicio_pal..fin_pal], &mut m);
+
+            i = fin_pal;
+            continue;
+        }
+
+        // ── Celdas no-palabra (puntuación, números, etc.) ──
+        // Para prefijos: asegurar que el par (prefijo + carácter) no se parta.
+        if celda.es_prefijo {
+            let siguiente_size = if i + 1 < n { 1usize } else { 0 };
+            let needed = 1 + siguiente_size;
+            while m.restante() < needed {
+                m.cerrar_linea();
+            }
+            m.push(celda.byte);
+            i += 1;
+            if i < n && siguiente_size > 0 {
+                m.push(celdas[i].byte);
+                i += 1;
+            }
+        } else if celda.guion_compuesta {
+            // Una palabra compuesta solo se parte por su guion, que se repite
+            // al comienzo de la línea siguiente (franco- / -alemán)
+            while m.restante() == 0 {
+                m.cerrar_linea();
+            }
+            m.push(celda.byte);
+            i += 1;
+            let parte = celdas[i..].iter()
+                .take_while(|c| c.salto.is_none() && !c.guion_compuesta && (c.byte != 0x00 || c.es_prefijo))
+                .count();
+            if parte > m.restante() {
+                m.cerrar_linea();
+                m.push(celda.byte);
+            }
+        } else {
+            while m.restante() == 0 {
+                m.cerrar_linea();
+            }
+            m.push(celda.byte);
+            i += 1;
+        }
+    }
+
+    m.terminar()
+}
+
+/// Serializa las páginas al formato .bin: los bytes de celda de cada línea
+/// seguidos del byte de control de salto de línea, y el byte de salto de
+/// página al final de cada página.
+pub fn paginas_a_bin(paginas: &[Pagina]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for pagina in paginas {
+        for linea in &pagina.lineas {
+            bytes.extend_from_slice(linea);
+            bytes.push(CTRL_SALTO_LINEA);
+        }
+        bytes.push(CTRL_SALTO_PAGINA);
+    }
+    bytes
+}
+
+/// Cada línea como texto en braille Unicode, conservando el relleno.
+pub fn linea_a_unicode(linea: &[u8]) -> String {
+    linea.iter().map(|&b| byte_a_unicode(b)).collect()
+}
+
+/// Archivo .txt en braille Unicode: una línea por fila, sin el relleno final,
+/// y salto de página (form feed) al final de cada página.
+pub fn paginas_a_txt(paginas: &[Pagina]) -> String {
+    let mut txt = String::new();
+    for pagina in paginas {
+        for linea in &pagina.lineas {
+            txt.push_str(linea_a_unicode(linea).trim_end_matches('\u{2800}'));
+            txt.push('\n');
+        }
+        txt.push('\u{000C}');
+    }
+    txt
+}
+
+/// Coloca las celdas de una palabra respetando silabación y reglas ONCE.
+fn colocar_palabra(celdas: &[CeldaInfo], m: &mut Maquetador) {
+    // Si la palabra cabe completa en el espacio restante → colocarla directa
+    let total = celdas.len();
+    let restante = m.restante();
+    if total <= restante {
+        for c in celdas {
+            m.push(c.byte);
+        }
+        return;
+    }
+
+    // La palabra no cabe completa. Intentar división silábica.
+    // Identificar los puntos de corte (inicio de sílaba) dentro de `celdas`.
+    // Un punto de corte es válido si: es inicio_silaba && !es_prefijo
+    // (nunca cortamos dejando un prefijo al final).
+    // Además, el corte debe dejar al menos una celda en la línea siguiente.
+
+    // Recopilar posiciones de inicio de sílaba
+    let mut cortes: Vec<usize> = Vec::new(); // índices dentro de `celdas`
+    for (k, c) in celdas.iter().enumerate() {
+        if k > 0 && c.inicio_silaba && !c.es_prefijo {
+            // Verificar que la celda anterior NO es prefijo (si lo es, no podemos
+            // cortar entre el prefijo y su carácter)
+            if !celdas[k - 1].es_prefijo {
+                cortes.push(k);
+            }
+        }
+    }
+
+    // Buscar el último corte que quepa reservando sitio para el guion
+    // de división (1 celda en CBE).
+    let espacio_con_guion = restante.saturating_sub(m.guion.len());
+
+    let mut corte_elegido: Option<usize> = None;
+    for &k in cortes.iter().rev() {
+        if k <= espacio_con_guion {
+            // Hay al menos `celdas.len() - k` celdas para la línea siguiente
+            if celdas.len() - k >= 1 {
+                corte_elegido = Some(k);
+                break;
+            }
+        }
+    }
+
+    if let Some(k) = corte_elegido {
+        // Colocar la primera parte + guion de división
+        for c in &celdas[..k] {
+            m.push(c.byte);
+        }
+        for byte in m.guion.clone() {
+            m.push(byte);
+        }
+        m.cerrar_linea();
+
+        // Colocar el resto de la palabra en la nueva línea (recursivo)
+        colocar_palabra(&celdas[k..], m);
+    } else {
+        // No hay corte silábico posible: trasladar la palabra entera a la línea siguiente
+        // (regla ONCE: si no se puede dividir correctamente, pasar íntegra)
+        if !m.linea.is_empty() || m.restante() == 0 {
+            m.cerrar_linea();
+        }
+        // Ahora intentar colocar en la línea fresca
+        // Si la palabra es más larga que toda una línea, dividir forzosamente
+        // (caso extremo; en braille muy poco probable)
+        if total > m.restante() {
+            // División forzada sin guion (caso degenerado)
+            let mut j = 0;
+            while j < total {
+                if m.restante() == 0 {
+                    m.cerrar_linea();
+                    continue;
+                }
+                let chunk = std::cmp::min(m.restante(), total - j);
+                for c in &celdas[j..j + chunk] {
+                    m.push(c.byte);
+                }
+                j += chunk;
+            }
+        } else {
+            for c in celdas {
+                m.push(c.byte);
+            }
+        }
+    }
+}
+
+// ─── Lector de epub universal vía content.opf ────────────────────────────────
+
+/// Contenido de una entrada del zip como texto UTF-8; `None` si no existe.
+fn leer_entrada<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    ruta: &str,
+) -> Result<Option<String>, ErrorBraille> {
+    let mut entry = match archive.by_name(ruta) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(ErrorBraille::CapituloIlegible {
+            ruta: ruta.to_string(),
+            detalle: e.to_string(),
+        }),
+    };
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).map_err(|e| ErrorBraille::CapituloIlegible {
+        ruta: ruta.to_string(),
+        detalle: e.to_string(),
+    })?;
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|_| ErrorBraille::Codificacion(ruta.to_string()))
+}
+
+/// Lee el EPUB completo: documento en orden de lectura y metadatos del OPF.
+pub fn leer_epub(epub_path: &str) -> Result<(Documento, Metadatos), ErrorBraille> {
+    let file = File::open(epub_path).map_err(|e| ErrorBraille::Apertura {
+        ruta: epub_path.to_string(),
+        detalle: e.to_string(),
+    })?;
+    leer_epub_de(file)
+}
+
+/// `leer_epub` sobre cualquier lector del zip (un archivo, bytes en memoria).
+fn leer_epub_de<R: Read + Seek>(lector: R) -> Result<(Documento, Metadatos), ErrorBraille> {
+    let mut archive = ZipArchive::new(lector)
+        .map_err(|e| ErrorBraille::EpubInvalido(e.to_string()))?;
+
+    let container = leer_entrada(&mut archive, "META-INF/container.xml")?
+        .ok_or(ErrorBraille::SinContainer)?;
+    let opf_path = opf::leer_container(&container)?;
+    let opf = leer_entrada(&mut archive, &opf_path)?
+        .ok_or_else(|| ErrorBraille::SinOpf(opf_path.clone()))?;
+    let paquete = Paquete::desde_xml(&opf, &opf_path)?;
+
+    // Las hojas de estilo se aplican a todos los capítulos: las clases de
+    // resalte suelen estar en una hoja común. Una que falte o no se pueda
+    // leer solo deja sin resalte sus clases.
+    let mut estilos = EstilosCss::default();
+    for path in paquete.hojas_de_estilo() {
+        if let Ok(Some(css)) = leer_entrada(&mut archive, &path) {
+            estilos.anadir(&css);
+        }
+    }
+
+    // Un capítulo del spine que no está en el zip se omite, como hacen los
+    // lectores; uno que está pero no se puede leer es un error.
+    let mut documento = Documento::default();
+    for path in paquete.documentos_spine() {
+        if let Some(content) = leer_entrada(&mut archive, &path)? {
+            documento.anadir_capitulo(Documento::desde_xhtml_con_estilos(&content, &estilos));
+        }
+    }
+
+    Ok((documento, paquete.metadatos))
+}
+
+// ─── Formatos de salida ──────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatoSalida {
+    /// Celdas de 1 byte + byte de control por línea (formato propio).
+    #[default]
+    Bin,
+    /// North American ASCII Braille.
+    Brf,
+    /// Portable Embosser Format (PEF 2008-1).
+    Pef,
+    /// Texto plano en braille Unicode (U+2800).
+    Txt,
+}
+
+impl FormatoSalida {
+    pub fn extension(self) -> &'static str {
+        match self {
+            FormatoSalida::Bin => "bin",
+            FormatoSalida::Brf => "brf",
+            FormatoSalida::Pef => "pef",
+            FormatoSalida::Txt => "txt",
+        }
+    }
+}
+
+/// Resumen de una conversión para mostrar en la interfaz.
+#[derive(Clone, Debug, Serialize)]
+pub struct Conversion {
+    pub ruta: String,
+    pub lineas: usize,
+    pub paginas: usize,
+    /// Tabla con la que se codificó (la elegida o la del idioma del libro).
+    pub tabla: String,
+    pub informe: Informe,
+}
+
+impl Conversion {
+    fn new(ruta: String, paginas: &[Pagina], tabla: &dyn TablaBraille, informe: Informe) -> Self {
+        Conversion {
+            ruta,
+            lineas: paginas.iter().map(|p| p.lineas.len()).sum(),
+            paginas: paginas.len(),
+            tabla: tabla.nombre().to_string(),
+            informe,
+        }
+    }
+}
+
+/// Metadatos Dublin Core del libro, tal como aparecen en content.opf.
+#[derive(Clone, Debug, Default)]
+pub struct Metadatos {
+    pub titulo: String,
+    pub autor: String,
+    pub idioma: String,
+    pub identificador: String,
+}
+
+/// Bytes del archivo de salida en el formato pedido.
+pub fn serializar(
+    paginas: &[Pagina],
+    formato: FormatoSalida,
+    maquetacion: &Maquetacion,
+    metadatos: &Metadatos,
+) -> Vec<u8> {
+    match formato {
+        FormatoSalida::Bin => paginas_a_bin(paginas),
+        FormatoSalida::Brf => brf::paginas_a_brf(paginas).into_bytes(),
+        FormatoSalida::Pef => pef::paginas_a_pef(paginas, maquetacion, metadatos).into_bytes(),
+        FormatoSalida::Txt => paginas_a_txt(paginas).into_bytes(),
+    }
+}
+
+/// Convierte el EPUB y deja el resultado a su lado, como `<libro>_braille.<ext>`.
+/// Sin tabla, se usa la del idioma declarado en el libro (`dc:language`).
+pub fn convertir_epub(
+    epub_path: &str,
+    formato: FormatoSalida,
+    maquetacion: &Maquetacion,
+    codificacion: &OpcionesCodificacion,
+    tabla: Option<&dyn TablaBraille>,
+) -> Result<Conversion, ErrorBraille> {
+    let salida_path = ruta_salida(epub_path, formato);
+    convertir_epub_en(epub_path, &salida_path, formato, maquetacion, codificacion, tabla)
+}
+
+/// Ruta en la que `convertir_epub` deja el resultado: `<libro>_braille.<ext>`.
+pub fn ruta_salida(epub_path: &str, formato: FormatoSalida) -> String {
+    format!("{}_braille.{}", epub_path.trim_end_matches(".epub"), formato.extension())
+}
+
+/// Igual que `convertir_epub`, pero escribiendo en `salida_path`.
+pub fn convertir_epub_en(
+    epub_path: &str,
+    salida_path: &str,
+    formato: FormatoSalida,
+    maquetacion: &Maquetacion,
+    codificacion: &OpcionesCodificacion,
+    tabla: Option<&dyn TablaBraille>,
+) -> Result<Conversion, ErrorBraille> {
+    maquetacion.validar()?;
+
+    let (documento, metadatos) = leer_epub(epub_path)?;
+    let del_idioma;
+    let tabla = match tabla {
+        Some(tabla) => tabla,
+        None => {
+            del_idioma = tabla_para_idioma(&metadatos.idioma);
+            del_idioma.as_ref()
+        }
+    };
+    let (celdas_info, informe) = codificar_documento(&documento, tabla, codificacion);
+    let paginas = formatear_paginas(&celdas_info, maquetacion, tabla);
+    let bytes = serializar(&paginas, formato, maquetacion, &metadatos);
+
+    fs::write(salida_path, &bytes).map_err(|e| ErrorBraille::Escritura {
+        ruta: salida_path.to_string(),
+        detalle: e.to_string(),
+    })?;
+
+    Ok(Conversion::new(salida_path.to_string(), &paginas, tabla, informe))
+}
+
+// ─── API pública para el editor ──────────────────────────────────────────────
+
+pub fn extraer_texto_epub(epub_path: &str) -> Result<String, ErrorBraille> {
+    leer_epub(epub_path).map(|(documento, _)| documento.a_texto())
+}
+
+pub fn convertir_texto(
+    texto: &str,
+    salida_path: &str,
+    formato: FormatoSalida,
+    maquetacion: &Maquetacion,
+    codificacion: &OpcionesCodificacion,
+    tabla: &dyn TablaBraille,
+) -> Result<Conversion, ErrorBraille> {
+    maquetacion.validar()?;
+
+    // El editor no conserva el content.opf: el título sale del nombre del archivo.
+    let titulo = std::path::Path::new(salida_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().trim_end_matches("_braille").to_string())
+        .unwrap_or_default();
+    let metadatos = Metadatos { titulo, ..Metadatos::default() };
+
+    let (celdas, informe) = codificar_documento(&Documento::desde_texto(texto), tabla, codificacion);
+    let paginas = formatear_paginas(&celdas, maquetacion, tabla);
+    let bytes = serializar(&paginas, formato, maquetacion, &metadatos);
+    fs::write(salida_path, &bytes).map_err(|e| ErrorBraille::Escritura {
+        ruta: salida_path.to_string(),
+        detalle: e.to_string(),
+    })?;
+    Ok(Conversion::new(salida_path.to_string(), &paginas, tabla, informe))
+}
+
+/// Páginas formateadas, cada una como sus líneas en braille Unicode, para la
+/// vista previa del editor.
+pub fn previsualizar_texto(
+    texto: &str,
+    maquetacion: &Maquetacion,
+    codificacion: &OpcionesCodificacion,
+    tabla: &dyn TablaBraille,
+) -> Result<Vec<Vec<String>>, ErrorBraille> {
+    maquetacion.validar()?;
+    let (celdas, _) = codificar_documento(&Documento::desde_texto(texto), tabla, codificacion);
+    let paginas = formatear_paginas(&celdas, maquetacion, tabla);
+    Ok(paginas.iter()
+        .map(|p| p.lineas.iter().map(|l| linea_a_unicode(l)).collect())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatear(texto: &str, maquetacion: &Maquetacion) -> Vec<Pagina> {
+        let documento = Documento::desde_texto(texto);
+        let (celdas, _) = codificar_documento(&documento, &TablaEspanol, &OpcionesCodificacion::default());
+        formatear_paginas(&celdas, maquetacion, &TablaEspanol)
+    }
+
+    /// Cada página como sus líneas en braille Unicode.
+    fn paginas(texto: &str, maquetacion: &Maquetacion) -> Vec<Vec<String>> {
+        formatear(texto, maquetacion).iter()
+            .map(|p| p.lineas.iter().map(|l| linea_a_unicode(l)).collect())
+            .collect()
+    }
+
+    fn lineas(texto: &str, ancho: usize) -> Vec<String> {
+        let maquetacion = Maquetacion {
+            celdas_por_linea: ancho,
+            numeracion: NumeracionPagina::Ninguna,
+            sangria: 0,
+            ..Maquetacion::default()
+        };
+        paginas(texto, &maquetacion).concat()
+    }
+
+    /// Páginas de `lineas_por_pagina` líneas de 10 celdas, sin sangría.
+    fn pagina_pequena(lineas_por_pagina: usize, numeracion: NumeracionPagina) -> Maquetacion {
+        Maquetacion {
+            celdas_por_linea: 10,
+            lineas_por_pagina,
+            numeracion,
+            sangria: 0,
+            ..Maquetacion::default()
+        }
+    }
+
+    /// `celdas` rellenas con celdas en blanco hasta `ancho`.
+    fn fila(celdas: &str, ancho: usize) -> String {
+        let mut fila = celdas.to_string();
+        fila.extend(std::iter::repeat_n('⠀', ancho - celdas.chars().count()));
+        fila
+    }
+
+    #[test]
+    fn celdas_en_braille_unicode() {
+        assert_eq!(byte_a_unicode(0), '\u{2800}');
+        assert_eq!(byte_a_unicode(puntos_a_byte(&[1])), '\u{2801}');
+        assert_eq!(byte_a_unicode(puntos_a_byte(&[3, 4, 5, 6])), '\u{283C}');
+        assert_eq!(byte_a_unicode(puntos_a_byte(&[1, 2, 3, 4, 5, 6])), '\u{283F}');
+        assert_eq!(linea_a_unicode(&[puntos_a_byte(&[4, 6]), puntos_a_byte(&[1]), 0]), "⠨⠁⠀");
+    }
+
+    #[test]
+    fn texto_en_braille_unicode() {
+        let a = puntos_a_byte(&[1]);
+        let paginas = [
+            Pagina { numero: 1, lineas: vec![vec![a, 0, a, 0], vec![0; 4]] },
+            Pagina { numero: 2, lineas: vec![vec![0, a, 0, 0]] },
+        ];
+        // Sin el relleno final; cada página termina en form feed
+        assert_eq!(paginas_a_txt(&paginas), "⠁⠀⠁\n\n\u{c}⠀⠁\n\u{c}");
+    }
+
+    #[test]
+    fn vista_previa() {
+        let maquetacion = pagina_pequena(2, NumeracionPagina::Ninguna);
+        let vista = previsualizar_texto("a\nb\nc", &maquetacion, &OpcionesCodificacion::default(), &TablaEspanol)
+            .unwrap();
+        assert_eq!(vista, [vec![fila("⠁", 10), fila("⠃", 10)], vec![fila("⠉", 10)]]);
+
+        let estrecha = Maquetacion { celdas_por_linea: 1, ..maquetacion };
+        let error = previsualizar_texto("a", &estrecha, &OpcionesCodificacion::default(), &TablaEspanol);
+        assert!(matches!(error, Err(ErrorBraille::Maquetacion(_))));
+    }
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+
+    const OPF: &str = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:language>es</dc:language></metadata>
+  <manifest><item id="c1" href="cap1.xhtml" media-type="application/xhtml+xml"/></manifest>
+  <spine><itemref idref="c1"/></spine>
+</package>"#;
+
+    const CAPITULO: &str = "<html><body><p>Hola</p></body></html>";
+
+    /// EPUB en memoria con estas entradas.
+    fn epub(entradas: &[(&str, &[u8])]) -> std::io::Cursor<Vec<u8>> {
+        use std::io::Write;
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (ruta, contenido) in entradas {
+            zip.start_file(*ruta, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(contenido).unwrap();
+        }
+        zip.finish().unwrap()
+    }
+
+    #[test]
+    fn epub_en_memoria() {
+        let (documento, metadatos) = leer_epub_de(epub(&[
+            ("META-INF/container.xml", CONTAINER.as_bytes()),
+            ("OEBPS/content.opf", OPF.as_bytes()),
+            ("OEBPS/cap1.xhtml", CAPITULO.as_bytes()),
+        ])).unwrap();
+        assert_eq!(documento.a_texto(), "Hola\n");
+        assert_eq!(metadatos.idioma, "es");
+    }
+
+    #[test]
+    fn clases_de_resalte_de_la_hoja_de_estilo() {
+        let opf = OPF.replace("<manifest>",
+            r#"<manifest><item id="css" href="estilo.css" media-type="text/css"/>"#);
+        let (documento, _) = leer_epub_de(epub(&[
+            ("META-INF/container.xml", CONTAINER.as_bytes()),
+            ("OEBPS/content.opf", opf.as_bytes()),
+            ("OEBPS/estilo.css", b".it { font-style: italic }"),
+            ("OEBPS/cap1.xhtml", b"<html><body><p>Hola <span class=\"it\">mundo</span></p></body></html>"),
+        ])).unwrap();
+        let texto: Vec<char> = documento.bloques[0].texto.chars().collect();
+        let (_, tramos) = resalte::separar(&texto, &Enfasis::default());
+        assert_eq!(tramos.len(), 1);
+        assert_eq!((tramos[0].inicio, tramos[0].fin), (5, 10));
+    }
+
+    #[test]
+    fn epub_mal_formado() {
+        let error = leer_epub_de(std::io::Cursor::new(b"PK no es un zip".to_vec())).unwrap_err();
+        assert!(matches!(error, ErrorBraille::EpubInvalido(_)), "{:?}", error);
+
+        let error = leer_epub_de(epub(&[
+            ("OEBPS/content.opf", OPF.as_bytes()),
+            ("OEBPS/cap1.xhtml", CAPITULO.as_bytes()),
+        ])).unwrap_err();
+        assert!(matches!(error, ErrorBraille::SinContainer), "{:?}", error);
+
+        let error = leer_epub_de(epub(&[
+            ("META-INF/container.xml", CONTAINER.as_bytes()),
+            ("OEBPS/cap1.xhtml", CAPITULO.as_bytes()),
+        ])).unwrap_err();
+        assert!(matches!(&error, ErrorBraille::SinOpf(ruta) if ruta == "OEBPS/content.opf"), "{:?}", error);
+
+        // "canción" en Latin-1
+        let error = leer_epub_de(epub(&[
+            ("META-INF/container.xml", CONTAINER.as_bytes()),
+            ("OEBPS/content.opf", OPF.as_bytes()),
+            ("OEBPS/cap1.xhtml", b"<html><body><p>canci\xf3n</p></body></html>"),
+        ])).unwrap_err();
+        assert!(matches!(&error, ErrorBraille::Codificacion(ruta) if ruta == "OEBPS/cap1.xhtml"), "{:?}", error);
+    }
+
+    #[test]
+    fn sin_espacio_tras_una_linea_llena() {
+        // "el acuerdo" ocupa justo las 10 celdas
+        let lineas = lineas("el acuerdo grande", 10);
+        assert_eq!(lineas[0], "⠑⠇⠀⠁⠉⠥⠑⠗⠙⠕");
+        assert_eq!(lineas[1], "⠛⠗⠁⠝⠙⠑⠀⠀⠀⠀");
+    }
+
+    #[test]
+    fn maquetacion_fuera_de_rango() {
+        let rechazada = |maquetacion: Maquetacion| {
+            matches!(maquetacion.validar(), Err(ErrorBraille::Maquetacion(_)))
+        };
+        assert!(Maquetacion::default().validar().is_ok());
+        assert!(rechazada(Maquetacion { celdas_por_linea: 1, sangria: 0, ..Maquetacion::default() }));
+        assert!(Maquetacion { celdas_por_linea: 2, sangria: 0, ..Maquetacion::default() }.validar().is_ok());
+        // La línea del número de página necesita otra línea para el texto
+        assert!(rechazada(Maquetacion { lineas_por_pagina: 1, ..Maquetacion::default() }));
+        assert!(rechazada(Maquetacion { lineas_por_pagina: 0, numeracion: NumeracionPagina::Ninguna, ..Maquetacion::default() }));
+        assert!(Maquetacion { lineas_por_pagina: 1, numeracion: NumeracionPagina::Ninguna, ..Maquetacion::default() }
+            .validar().is_ok());
+        assert!(rechazada(Maquetacion { celdas_por_linea: 10, sangria: 10, ..Maquetacion::default() }));
+        assert!(rechazada(Maquetacion { celdas_por_linea: 10, sangria: 11, ..Maquetacion::default() }));
+        assert!(Maquetacion { celdas_por_linea: 10, sangria: 9, ..Maquetacion::default() }.validar().is_ok());
+        assert!(rechazada(Maquetacion { nivel_capitulo: 7, ..Maquetacion::default() }));
+    }
+
+    #[test]
+    fn el_ancho_decide_donde_se_parte() {
+        let texto = "la casa de la pradera";
+        assert_eq!(lineas(texto, 30), [fila("⠇⠁⠀⠉⠁⠎⠁⠀⠙⠑⠀⠇⠁⠀⠏⠗⠁⠙⠑⠗⠁", 30)]);
+        assert_eq!(lineas(texto, 12), [fila("⠇⠁⠀⠉⠁⠎⠁⠀⠙⠑", 12), fila("⠇⠁⠀⠏⠗⠁⠙⠑⠗⠁", 12)]);
+        // Con 6 celdas "camino" se parte por sílabas, con guion de división
+        assert_eq!(lineas("el camino", 6), [fila("⠑⠇⠀⠉⠁⠤", 6), fila("⠍⠊⠝⠕", 6)]);
+        assert_eq!(lineas("el camino", 9), [fila("⠑⠇⠀⠉⠁⠍⠊⠝⠕", 9)]);
+    }
+
+    #[test]
+    fn compuesta_partida_por_su_guion() {
+        // "franco-" llena la línea: "alemán" pasa entero a la siguiente,
+        // precedido otra vez por el guion
+        assert_eq!(lineas("el franco-alemán", 10), [fila("⠑⠇⠀⠋⠗⠁⠝⠉⠕⠤", 10), fila("⠤⠁⠇⠑⠍⠷⠝", 10)]);
+        // Si cabe entera, el guion no se repite
+        assert_eq!(lineas("franco-alemán", 14), [fila("⠋⠗⠁⠝⠉⠕⠤⠁⠇⠑⠍⠷⠝", 14)]);
+        // Un guion de diálogo no es el de una compuesta
+        let compuestas = |texto: &str| codificar_texto(texto, &TablaEspanol).iter()
+            .filter(|c| c.guion_compuesta).count();
+        assert_eq!(compuestas("franco-alemán"), 1);
+        assert_eq!(compuestas("dijo -hola- y"), 0);
+    }
+
+    #[test]
+    fn salto_de_pagina_tras_lineas_por_pagina() {
+        let maquetacion = pagina_pequena(3, NumeracionPagina::Ninguna);
+        let resultado = formatear("a\nb\nc\nd\ne", &maquetacion);
+        assert_eq!(resultado.iter().map(|p| p.numero).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(paginas("a\nb\nc\nd\ne", &maquetacion), [
+            vec![fila("⠁", 10), fila("⠃", 10), fila("⠉", 10)],
+            vec![fila("⠙", 10), fila("⠑", 10)],
+        ]);
+    }
+
+    #[test]
+    fn numero_de_pagina_superior() {
+        // Prefijo numérico (3456) y dígitos, pegados al margen derecho
+        let maquetacion = pagina_pequena(3, NumeracionPagina::Superior);
+        assert_eq!(paginas("a\nb\nc\nd", &maquetacion), [
+            vec!["⠁⠀⠀⠀⠀⠀⠀⠀⠼⠁".to_string(), fila("⠃", 10), fila("⠉", 10)],
+            vec!["⠙⠀⠀⠀⠀⠀⠀⠀⠼⠃".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn numero_de_pagina_inferior_completa_la_ultima_pagina() {
+        let maquetacion = pagina_pequena(3, NumeracionPagina::Inferior);
+        assert_eq!(paginas("a\nb\nc\nd", &maquetacion), [
+            vec![fila("⠁", 10), fila("⠃", 10), "⠉⠀⠀⠀⠀⠀⠀⠀⠼⠁".to_string()],
+            vec![fila("⠙", 10), fila("", 10), "⠀⠀⠀⠀⠀⠀⠀⠀⠼⠃".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn titulo_de_capitulo_centrado() {
+        // "⠨⠥⠝⠕" ocupa 4 celdas: (20 - 4) / 2 = 8 de margen
+        let maquetacion = Maquetacion { celdas_por_linea: 20, ..pagina_pequena(25, NumeracionPagina::Ninguna) };
+        assert_eq!(paginas("# Uno", &maquetacion)[0][0], format!("{}⠨⠥⠝⠕{}", "⠀".repeat(8), "⠀".repeat(8)));
+        // En la línea del número se centra en el sitio que queda: (15 - 4) / 2
+        let maquetacion = Maquetacion { numeracion: NumeracionPagina::Superior, ..maquetacion };
+        assert_eq!(paginas("# Uno", &maquetacion)[0][0], format!("{}⠨⠥⠝⠕{}⠼⠁", "⠀".repeat(5), "⠀".repeat(9)));
+        // Los encabezados de nivel inferior al de capítulo no se centran
+        assert_eq!(paginas("## Uno", &maquetacion)[0][0], format!("⠨⠥⠝⠕{}⠼⠁", "⠀".repeat(14)));
+    }
+
+    #[test]
+    fn lineas_en_blanco_alrededor_del_titulo() {
+        let maquetacion = Maquetacion {
+            centrar_titulos: false,
+            lineas_antes_titulo: 2,
+            lineas_despues_titulo: 1,
+            ..pagina_pequena(25, NumeracionPagina::Ninguna)
+        };
+        assert_eq!(paginas("a\n# B\nc", &maquetacion), [vec![
+            fila("⠁", 10), fila("", 10), fila("", 10), fila("⠨⠃", 10), fila("", 10), fila("⠉", 10),
+        ]]);
+        // Ni al comienzo del libro ni al comienzo de una página
+        assert_eq!(paginas("# B\nc", &maquetacion), [vec![fila("⠨⠃", 10), fila("", 10), fila("⠉", 10)]]);
+        let maquetacion = Maquetacion { lineas_por_pagina: 2, ..maquetacion };
+        assert_eq!(paginas("a\n# B", &maquetacion), [
+            vec![fila("⠁", 10), fila("", 10)],
+            vec![fila("⠨⠃", 10)],
+        ]);
+    }
+
+    #[test]
+    fn capitulo_en_pagina_nueva() {
+        let maquetacion = Maquetacion {
+            centrar_titulos: false,
+            capitulo_en_pagina_nueva: true,
+            ..pagina_pequena(25, NumeracionPagina::Ninguna)
+        };
+        assert_eq!(paginas("a\n# B\n## C\nd", &maquetacion), [
+            vec![fila("⠁", 10)],
+            vec![fila("⠨⠃", 10), fila("", 10), fila("⠨⠉", 10), fila("", 10), fila("⠙", 10)],
+        ]);
+        // Un capítulo al comienzo de una página no deja otra en blanco
+        assert_eq!(paginas("# B\nd", &maquetacion).len(), 1);
+        let hasta_h2 = Maquetacion { nivel_capitulo: 2, ..maquetacion };
+        assert_eq!(paginas("a\n# B\n## C", &hasta_h2), [
+            vec![fila("⠁", 10)],
+            vec![fila("⠨⠃", 10)],
+            vec![fila("⠨⠉", 10)],
+        ]);
+    }
+
+    #[test]
+    fn el_texto_no_llega_al_numero_de_pagina() {
+        // En la línea del número caben 10 - 3 de separación - 2 del número:
+        // "casa" y su espacio, pero no "mesa"
+        let maquetacion = pagina_pequena(2, NumeracionPagina::Superior);
+        let texto = "casa mesa ".repeat(20);
+        let resultado = formatear(&texto, &maquetacion);
+        assert_eq!(linea_a_unicode(&resultado[0].lineas[0]), "⠉⠁⠎⠁⠀⠀⠀⠀⠼⠁");
+        assert!(resultado.len() >= 10, "{} páginas", resultado.len());
+        for pagina in &resultado {
+            let numero = celdas_numero_pagina(pagina.numero, &TablaEspanol);
+            let linea = &pagina.lineas[0];
+            assert_eq!(linea.len(), 10);
+            assert_eq!(linea[10 - numero.len()..], numero[..]);
+            let separacion = 10 - numero.len() - SEPARACION_NUMERO_PAGINA..10 - numero.len();
+            assert!(linea[separacion].iter().all(|&b| b == 0x00),
+                "página {}: {}", pagina.numero, linea_a_unicode(linea));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// ─── Normalización del texto antes de codificar ──────────────────────────────
+// Los EPUB no siempre guardan el texto como lo espera la tabla: acentos
+// descompuestos (a + U+0301), ligaduras tipográficas, espacios especiales,
+// caracteres invisibles y guiones blandos. Este paso deja cada carácter en la
+// forma que la tabla conoce; cada ajuste se puede desactivar.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// Guion blando (U+00AD): indica dónde se puede partir una palabra.
+pub const GUION_BLANDO: char = '\u{00AD}';
+
+/// Qué hacer con los guiones blandos del libro.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GuionBlando {
+    /// Se quitan; las palabras se parten por el silabeo de la tabla.
+    #[default]
+    Quitar,
+    /// Se conservan como los únicos puntos de corte de su palabra.
+    PuntoDeCorte,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Normalizacion {
+    /// Composición canónica (NFC): a + U+0301 → á.
+    pub componer: bool,
+    /// Ligaduras tipográficas a sus letras: ﬁ → fi, ﬂ → fl.
+    pub ligaduras: bool,
+    /// Espacios especiales (sin separación, finos, ideográficos, tabulador)
+    /// al espacio normal, y fuera los caracteres de ancho cero.
+    pub espacios: bool,
+    pub guion_blando: GuionBlando,
+    /// Comillas tipográficas a las rectas (“ ” « » → ", ‘ ’ → '), para
+    /// tablas que solo conocen estas.
+    pub comillas_rectas: bool,
+}
+
+impl Default for Normalizacion {
+    fn default() -> Self {
+        Normalizacion {
+            componer: true,
+            ligaduras: true,
+            espacios: true,
+            guion_blando: GuionBlando::default(),
+            comillas_rectas: false,
+        }
+    }
+}
+
+/// Texto listo para el codificador.
+pub fn normalizar(texto: &str, opciones: &Normalizacion) -> String {
+    let compuesto: String = if opciones.componer {
+        texto.nfc().collect()
+    } else {
+        texto.to_string()
+    };
+
+    let mut resultado = String::with_capacity(compuesto.len());
+    for c in compuesto.chars() {
+        if opciones.ligaduras {
+            if let Some(letras) = expandir_ligadura(c) {
+                resultado.push_str(letras);
+                continue;
+            }
+        }
+        if opciones.espacios {
+            if es_ancho_cero(c) {
+                continue;
+            }
+            if es_espacio_especial(c) {
+                resultado.push(' ');
+                continue;
+            }
+        }
+        if c == GUION_BLANDO && opciones.guion_blando == GuionBlando::Quitar {
+            continue;
+        }
+        if opciones.comillas_rectas {
+            match c {
+                '“' | '”' | '„' | '‟' | '«' | '»' => {
+                    resultado.push('"');
+                    continue;
+                }
+                '‘' | '’' | '‚' | '‛' => {
+                    resultado.push('\'');
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        resultado.push(c);
+    }
+    resultado
+}
+
+fn expandir_ligadura(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{FB00}' => "ff",
+        '\u{FB01}' => "fi",
+        '\u{FB02}' => "fl",
+        '\u{FB03}' => "ffi",
+        '\u{FB04}' => "ffl",
+        '\u{FB05}' | '\u{FB06}' => "st",
+        '\u{0132}' => "IJ",
+        '\u{0133}' => "ij",
+        _ => return None,
+    })
+}
+
+/// Espacios y separadores que se escriben como un espacio normal. El
+/// separador de línea U+2028 no: marca los `<br>`.
+fn es_espacio_especial(c: char) -> bool {
+    matches!(c,
+        '\t' | '\u{00A0}' | '\u{1680}' | '\u{2000}'..='\u{200A}'
+        | '\u{202F}' | '\u{205F}' | '\u{3000}')
+}
+
+/// Caracteres de formato que no ocupan lugar: espacio de ancho cero,
+/// (no) unión de ancho cero, unión de palabras y marca de orden de bytes.
+fn es_ancho_cero(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codificar_documento, codificar_texto, Documento, OpcionesCodificacion, TablaEspanol};
+
+    fn normal(texto: &str) -> String {
+        normalizar(texto, &Normalizacion::default())
+    }
+
+    fn bytes(texto: &str) -> Vec<u8> {
+        codificar_texto(texto, &TablaEspanol).iter().map(|c| c.byte).collect()
+    }
+
+    #[test]
+    fn compone_acentos() {
+        assert_eq!(normal("cancio\u{0301}n"), "canción");
+        assert_eq!(normal("N\u{0303}andu\u{0301}"), "Ñandú");
+        assert_eq!(normal("pingu\u{0308}ino"), "pingüino");
+        // y el codificador ya no pierde el acento
+        assert_eq!(bytes("cancio\u{0301}n"), bytes("canción"));
+    }
+
+    #[test]
+    fn sin_componer() {
+        let opciones = Normalizacion { componer: false, ..Normalizacion::default() };
+        assert_eq!(normalizar("o\u{0301}", &opciones), "o\u{0301}");
+    }
+
+    #[test]
+    fn expande_ligaduras() {
+        assert_eq!(normal("\u{FB01}n \u{FB02}or e\u{FB00}ecto o\u{FB03}cina"), "fin flor effecto officina");
+        let opciones = Normalizacion { ligaduras: false, ..Normalizacion::default() };
+        assert_eq!(normalizar("\u{FB01}n", &opciones), "\u{FB01}n");
+    }
+
+    #[test]
+    fn pliega_espacios_e_invisibles() {
+        assert_eq!(normal("10\u{00A0}km"), "10 km");
+        assert_eq!(normal("a\u{202F}b\u{2009}c\u{3000}d\te"), "a b c d e");
+        assert_eq!(normal("ca\u{200B}sa\u{200D}\u{FEFF}"), "casa");
+        // el separador de línea de los <br> se respeta
+        assert_eq!(normal("a\u{2028}b"), "a\u{2028}b");
+        assert_eq!(bytes("10\u{00A0}km"), bytes("10 km"));
+
+        let opciones = Normalizacion { espacios: false, ..Normalizacion::default() };
+        assert_eq!(normalizar("a\u{00A0}b\u{200B}", &opciones), "a\u{00A0}b\u{200B}");
+    }
+
+    #[test]
+    fn guion_blando() {
+        assert_eq!(normal("ca\u{00AD}sa"), "casa");
+        assert_eq!(bytes("ca\u{00AD}sa"), bytes("casa"));
+
+        let opciones = Normalizacion { guion_blando: GuionBlando::PuntoDeCorte, ..Normalizacion::default() };
+        assert_eq!(normalizar("ca\u{00AD}sa", &opciones), "ca\u{00AD}sa");
+    }
+
+    #[test]
+    fn guion_blando_como_punto_de_corte() {
+        let opciones = OpcionesCodificacion {
+            normalizacion: Normalizacion { guion_blando: GuionBlando::PuntoDeCorte, ..Normalizacion::default() },
+            ..OpcionesCodificacion::default()
+        };
+        let documento = Documento::desde_texto("inter\u{00AD}na\u{00AD}cional");
+        let (celdas, informe) = codificar_documento(&documento, &TablaEspanol, &opciones);
+        let celdas: Vec<_> = celdas.iter().filter(|c| c.salto.is_none()).collect();
+
+        // Las celdas son las de la palabra sin guiones, y los únicos cortes
+        // son los del libro (el silabeo daría in-ter-na-cio-nal)
+        assert_eq!(celdas.iter().map(|c| c.byte).collect::<Vec<_>>(), bytes("internacional"));
+        let cortes: Vec<usize> = (0..celdas.len()).filter(|&k| celdas[k].inicio_silaba).collect();
+        assert_eq!(cortes, [5, 7]);
+        assert!(informe.sin_celda.is_empty());
+    }
+
+    #[test]
+    fn comillas_rectas() {
+        assert_eq!(normal("«hola»"), "«hola»");
+        let opciones = Normalizacion { comillas_rectas: true, ..Normalizacion::default() };
+        assert_eq!(normalizar("«a» “b” ‘c’", &opciones), "\"a\" \"b\" 'c'");
+    }
+}
@@ -0,0 +1,355 @@
+// ─── Notación numérica: ordinales, fracciones, índices y números romanos ─────
+// Reconocimiento de las construcciones que no se escriben carácter a carácter.
+// En CBE, ordinales y denominadores usan las cifras en la parte inferior de la
+// celda (1 → punto 2, 4 → puntos 2-5-6): "1.º" es ⠼⠂ y "3/4" es ⠼⠉⠲.
+
+use crate::tabla::{Indicador, TablaBraille};
+
+/// Celda de la cifra en la parte inferior: cada punto baja una fila
+/// (1 → 2, 2 → 3, 4 → 5, 5 → 6).
+pub fn bajar_celda(celda: u8) -> u8 {
+    (celda & 0b011011) << 1
+}
+
+fn es_cifra(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+/// Celdas de las cifras, en la parte superior o en la inferior.
+fn cifras(digitos: &[char], abajo: bool, tabla: &dyn TablaBraille) -> Option<Vec<u8>> {
+    let mut celdas = Vec::new();
+    for &d in digitos {
+        let celda = tabla.digito(d)?;
+        if abajo {
+            celdas.extend(celda.iter().map(|&c| bajar_celda(c)));
+        } else {
+            celdas.extend(celda);
+        }
+    }
+    Some(celdas)
+}
+
+fn largo_cifras(texto: &[char]) -> usize {
+    texto.iter().take_while(|&&c| es_cifra(c)).count()
+}
+
+/// Ordinal al comienzo del texto ("1.º", "2.ª", "21º"): cuántos caracteres
+/// abarca y sus celdas. El prefijo numérico y las cifras abajo; el femenino
+/// añade la a.
+pub fn ordinal(texto: &[char], tabla: &dyn TablaBraille) -> Option<(usize, Vec<u8>)> {
+    let n = largo_cifras(texto);
+    if n == 0 {
+        return None;
+    }
+    let punto = usize::from(texto.get(n) == Some(&'.'));
+    let indicador = *texto.get(n + punto)?;
+    if indicador != 'º' && indicador != 'ª' {
+        return None;
+    }
+
+    let mut celdas = tabla.indicador(Indicador::Numero);
+    celdas.extend(cifras(&texto[..n], true, tabla)?);
+    if indicador == 'ª' {
+        celdas.extend(tabla.letra('a')?);
+    }
+    Some((n + punto + 1, celdas))
+}
+
+/// Fracción con barra ("3/4", "15⁄16") al comienzo del texto: numerador
+/// arriba y denominador abajo, tras un solo prefijo. Una fecha ("12/05/2020")
+/// no es fracción.
+pub fn fraccion(texto: &[char], tabla: &dyn TablaBraille) -> Option<(usize, Vec<u8>)> {
+    let es_barra = |c: &char| *c == '/' || *c == '\u{2044}';
+    let numerador = largo_cifras(texto);
+    if numerador == 0 || !texto.get(numerador).is_some_and(es_barra) {
+        return None;
+    }
+    let denominador = largo_cifras(&texto[numerador + 1..]);
+    let fin = numerador + 1 + denominador;
+    if denominador == 0 || texto.get(fin).is_some_and(es_barra) {
+        return None;
+    }
+
+    let mut celdas = tabla.indicador(Indicador::Numero);
+    celdas.extend(cifras(&texto[..numerador], false, tabla)?);
+    celdas.extend(cifras(&texto[numerador + 1..fin], true, tabla)?);
+    Some((fin, celdas))
+}
+
+/// Numerador y denominador de un carácter de fracción (½, ¾, ⅛…).
+fn fraccion_unicode(c: char) -> Option<(&'static str, &'static str)> {
+    Some(match c {
+        '¼' => ("1", "4"),
+        '½' => ("1", "2"),
+        '¾' => ("3", "4"),
+        '⅐' => ("1", "7"),
+        '⅑' => ("1", "9"),
+        '⅒' => ("1", "10"),
+        '⅓' => ("1", "3"),
+        '⅔' => ("2", "3"),
+        '⅕' => ("1", "5"),
+        '⅖' => ("2", "5"),
+        '⅗' => ("3", "5"),
+        '⅘' => ("4", "5"),
+        '⅙' => ("1", "6"),
+        '⅚' => ("5", "6"),
+        '⅛' => ("1", "8"),
+        '⅜' => ("3", "8"),
+        '⅝' => ("5", "8"),
+        '⅞' => ("7", "8"),
+        _ => return None,
+    })
+}
+
+/// Celdas de un carácter de fracción, como si fuera "n/d".
+pub fn fraccion_caracter(c: char, tabla: &dyn TablaBraille) -> Option<Vec<u8>> {
+    let (numerador, denominador) = fraccion_unicode(c)?;
+    let texto: Vec<char> = format!("{}/{}", numerador, denominador).chars().collect();
+    fraccion(&texto, tabla).map(|(_, celdas)| celdas)
+}
+
+fn cifra_volada(c: char) -> Option<char> {
+    Some(match c {
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴' => '4',
+        '⁵' => '5',
+        '⁶' => '6',
+        '⁷' => '7',
+        '⁸' => '8',
+        '⁹' => '9',
+        _ => return None,
+    })
+}
+
+fn cifra_subindice(c: char) -> Option<char> {
+    let d = u32::from(c).checked_sub(0x2080)?;
+    if d <= 9 {
+        char::from_digit(d, 10)
+    } else {
+        None
+    }
+}
+
+/// Exponente ("x²", "10³") o subíndice ("H₂O") al comienzo del texto: el
+/// indicador de índice y el número con su prefijo.
+pub fn indice(texto: &[char], tabla: &dyn TablaBraille) -> Option<(usize, Vec<u8>)> {
+    let (convertir, indicador): (fn(char) -> Option<char>, _) = match texto.first() {
+        Some(&c) if cifra_volada(c).is_some() => (cifra_volada, Indicador::Superindice),
+        Some(&c) if cifra_subindice(c).is_some() => (cifra_subindice, Indicador::Subindice),
+        _ => return None,
+    };
+    let digitos: Vec<char> = texto.iter().map_while(|&c| convertir(c)).collect();
+
+    let mut celdas = tabla.indicador(indicador);
+    celdas.extend(tabla.indicador(Indicador::Numero));
+    celdas.extend(cifras(&digitos, false, tabla)?);
+    Some((digitos.len(), celdas))
+}
+
+// ─── Números romanos ─────────────────────────────────────────────────────────
+
+/// Palabras tras las que una palabra en mayúsculas que se lee como número
+/// romano lo es ("capítulo XIV", "siglo XX").
+const ANTES_DE_ROMANO: &[&str] = &[
+    "acto", "anexo", "apéndice", "artículo", "canto", "capítulo", "cap", "escena",
+    "lección", "libro", "parte", "siglo", "siglos", "tema", "título", "tomo", "volumen",
+    "vol",
+];
+
+/// Número romano bien formado (I…MMMCMXCIX), en mayúsculas.
+pub fn es_romano(palabra: &str) -> bool {
+    if palabra.is_empty() || !palabra.chars().all(|c| "IVXLCDM".contains(c)) {
+        return false;
+    }
+    // Cada orden (millares, centenas, decenas, unidades) con sus tres letras
+    let ordenes = [('M', ' ', ' '), ('C', 'D', 'M'), ('X', 'L', 'C'), ('I', 'V', 'X')];
+    let mut resto = palabra;
+    for (uno, cinco, diez) in ordenes {
+        let formas = [
+            format!("{uno}{diez}"),
+            format!("{uno}{cinco}"),
+            format!("{cinco}{uno}{uno}{uno}"),
+            format!("{cinco}{uno}{uno}"),
+            format!("{cinco}{uno}"),
+            format!("{cinco}"),
+            format!("{uno}{uno}{uno}"),
+            format!("{uno}{uno}"),
+            format!("{uno}"),
+        ];
+        if let Some(forma) = formas.iter().find(|f| !f.contains(' ') && resto.starts_with(f.as_str())) {
+            resto = &resto[forma.len()..];
+        }
+    }
+    resto.is_empty()
+}
+
+/// ¿La palabra en mayúsculas `palabra`, entre `antes` y `despues` en su
+/// bloque, es un número romano? Lo es tras una de `ANTES_DE_ROMANO`, tras un
+/// nombre propio si solo usa I, V y X ("Felipe II", "Juan XXIII"), o al
+/// comienzo de un título si va sola o la sigue un punto, dos puntos o un
+/// guion ("XIV. El regreso", pero no "MI VIDA"). Al comienzo de un título no
+/// se aceptan la D ni la M: ningún capítulo llega al CD, y así "MIX" o "DI"
+/// solos siguen siendo palabras.
+pub fn es_romano_en_contexto(palabra: &str, antes: &[char], despues: &[char], en_titulo: bool) -> bool {
+    if !es_romano(palabra) {
+        return false;
+    }
+    let previa: String = {
+        let fin = antes.iter().rposition(|c| c.is_alphanumeric()).map_or(0, |p| p + 1);
+        let inicio = antes[..fin].iter().rposition(|c| !c.is_alphabetic()).map_or(0, |p| p + 1);
+        antes[inicio..fin].iter().collect()
+    };
+    if previa.is_empty() {
+        let siguiente = despues.iter().find(|c| !c.is_whitespace());
+        let separado = siguiente.is_none_or(|c| ".:-\u{2013}\u{2014}".contains(*c));
+        let de_capitulo = palabra.chars().all(|c| "IVXLC".contains(c));
+        return en_titulo && separado && de_capitulo && !antes.iter().any(|c| c.is_alphanumeric());
+    }
+    if ANTES_DE_ROMANO.contains(&previa.to_lowercase().as_str()) {
+        return true;
+    }
+    let mut letras = previa.chars();
+    let nombre_propio = letras.next().is_some_and(char::is_uppercase)
+        && letras.all(char::is_lowercase);
+    nombre_propio && palabra.chars().all(|c| "IVX".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codificar_texto, puntos_a_byte, TablaEspanol};
+
+    fn p(puntos: &[u8]) -> u8 {
+        puntos_a_byte(puntos)
+    }
+
+    fn chars(texto: &str) -> Vec<char> {
+        texto.chars().collect()
+    }
+
+    const NUM: &[u8] = &[3, 4, 5, 6];
+
+    #[test]
+    fn cifras_abajo() {
+        assert_eq!(bajar_celda(p(&[1])), p(&[2]));
+        assert_eq!(bajar_celda(p(&[1, 4, 5])), p(&[2, 5, 6]));
+        assert_eq!(bajar_celda(p(&[2, 4, 5])), p(&[3, 5, 6]));
+    }
+
+    #[test]
+    fn ordinales() {
+        let t = &TablaEspanol;
+        assert_eq!(ordinal(&chars("1.º"), t), Some((3, vec![p(NUM), p(&[2])])));
+        assert_eq!(ordinal(&chars("21º"), t), Some((3, vec![p(NUM), p(&[2, 3]), p(&[2])])));
+        assert_eq!(ordinal(&chars("2.ª"), t), Some((3, vec![p(NUM), p(&[2, 3]), p(&[1])])));
+        assert_eq!(ordinal(&chars("2. a"), t), None);
+        assert_eq!(ordinal(&chars("1.5"), t), None);
+    }
+
+    #[test]
+    fn fracciones() {
+        let t = &TablaEspanol;
+        assert_eq!(fraccion(&chars("3/4"), t), Some((3, vec![p(NUM), p(&[1, 4]), p(&[2, 5, 6])])));
+        assert_eq!(fraccion(&chars("15⁄16 kg"), t).map(|f| f.0), Some(5));
+        assert_eq!(fraccion(&chars("12/05/2020"), t), None);
+        assert_eq!(fraccion(&chars("3/"), t), None);
+        assert_eq!(fraccion_caracter('½', t), Some(vec![p(NUM), p(&[1]), p(&[2, 3])]));
+        assert_eq!(fraccion_caracter('¾', t), fraccion(&chars("3/4"), t).map(|f| f.1));
+    }
+
+    #[test]
+    fn indices() {
+        let t = &TablaEspanol;
+        assert_eq!(indice(&chars("²"), t), Some((1, vec![p(&[1, 6]), p(NUM), p(&[1, 2])])));
+        assert_eq!(indice(&chars("₁₀"), t), Some((2, vec![p(&[3, 4]), p(NUM), p(&[1]), p(&[2, 4, 5])])));
+        assert_eq!(indice(&chars("2"), t), None);
+    }
+
+    #[test]
+    fn romanos() {
+        for r in ["I", "IV", "IX", "XIV", "XL", "XC", "CD", "MCMXCIX", "MMXXVI", "LXXXVIII"] {
+            assert!(es_romano(r), "{}", r);
+        }
+        for r in ["", "IIII", "VX", "IC", "XM", "MMMM", "CASA", "VV", "iv"] {
+            assert!(!es_romano(r), "{}", r);
+        }
+    }
+
+    #[test]
+    fn romanos_en_contexto() {
+        let antes = |t: &str| chars(t);
+        let nada: &[char] = &[];
+        assert!(es_romano_en_contexto("XIV", &antes("Capítulo "), nada, false));
+        assert!(es_romano_en_contexto("XX", &antes("en el siglo "), &antes(" fue"), false));
+        assert!(es_romano_en_contexto("II", &antes("Felipe "), nada, false));
+        assert!(es_romano_en_contexto("XIV", nada, nada, true));
+        assert!(es_romano_en_contexto("XIV", nada, &antes(". El regreso"), true));
+        assert!(es_romano_en_contexto("IV", nada, &antes(": Final"), true));
+        assert!(es_romano_en_contexto("IV", nada, &antes(" - Final"), true));
+        assert!(!es_romano_en_contexto("XIV", nada, nada, false));
+        assert!(!es_romano_en_contexto("MIL", &antes("Pedro "), nada, false));
+        assert!(!es_romano_en_contexto("DI", &antes("YO "), nada, false));
+        // Una palabra cualquiera que abre el título no es un número
+        for palabra in ["MI", "DI", "MIL", "CID", "VID"] {
+            assert!(!es_romano_en_contexto(palabra, nada, &antes(" VIDA"), true), "{}", palabra);
+        }
+
+        let mayuscula = p(&[4, 6]);
+        let titulo = |texto: &str| -> Vec<u8> {
+            let documento = crate::Documento::desde_texto(texto);
+            let (celdas, _) = crate::codificar_documento(&documento, &TablaEspanol, &Default::default());
+            celdas.iter().filter(|c| c.salto.is_none()).map(|c| c.byte).take(3).collect()
+        };
+        assert_eq!(titulo("# MI VIDA"), [mayuscula, p(&[1, 3, 4]), p(&[2, 4])]);
+        assert_eq!(titulo("# XIV. El regreso"), [mayuscula, mayuscula, p(&[1, 3, 4, 6])]);
+    }
+
+    #[test]
+    fn romanos_al_comienzo_del_titulo() {
+        let titulo = |texto: &str| -> Vec<u8> {
+            let documento = crate::Documento::desde_texto(texto);
+            let (celdas, _) = crate::codificar_documento(&documento, &TablaEspanol, &Default::default());
+            celdas.iter().filter(|c| c.salto.is_none()).map(|c| c.byte).collect()
+        };
+        let m = p(&[4, 6]);
+        let (i, v, x) = (p(&[2, 4]), p(&[1, 2, 3, 6]), p(&[1, 3, 4, 6]));
+        let dos_puntos = p(&[2, 5]);
+        let punto = p(&[3]);
+
+        // Tras "capítulo", aunque el título vaya en mayúsculas
+        let capitulo = [p(&[1, 4]), p(&[1]), p(&[1, 2, 3, 4]), p(&[3, 4]), p(&[2, 3, 4, 5]), p(&[1, 3, 6]),
+            p(&[1, 2, 3]), p(&[1, 3, 5])];
+        let fin = [p(&[1, 2, 4]), i, p(&[1, 3, 4, 5])];
+        assert_eq!(titulo("# CAPÍTULO IV: Fin"),
+                   [&[m][..], &capitulo, &[0, m, m, i, v, dos_puntos, 0, m], &fin].concat());
+        // Solo o ante un punto
+        assert_eq!(titulo("# IV."), [m, m, i, v, punto]);
+        assert_eq!(titulo("# XIV"), [m, m, x, i, v]);
+        // "I" seguida de otra palabra es la letra, con un solo signo de mayúscula
+        assert_eq!(titulo("# I am"), [m, i, 0, p(&[1]), p(&[1, 3, 4])]);
+        // "MIX" es una palabra, sola o seguida de otra
+        let mix = [m, p(&[1, 3, 4]), i, x];
+        assert_eq!(titulo("# MIX"), mix);
+        assert_eq!(titulo("# MIX de verano")[..4], mix);
+        assert_eq!(titulo("# MIX: verano")[..5], [&mix[..], &[dos_puntos]].concat());
+    }
+
+    #[test]
+    fn en_el_texto() {
+        let bytes = |texto: &str| -> Vec<u8> {
+            codificar_texto(texto, &TablaEspanol).iter().map(|c| c.byte).collect()
+        };
+        assert_eq!(bytes("1.º"), [p(NUM), p(&[2])]);
+        assert_eq!(bytes("3/4"), [p(NUM), p(&[1, 4]), p(&[2, 5, 6])]);
+        assert_eq!(bytes("½"), [p(NUM), p(&[1]), p(&[2, 3])]);
+        assert_eq!(bytes("x²"), [p(&[1, 3, 4, 6]), p(&[1, 6]), p(NUM), p(&[1, 2])]);
+        assert_eq!(bytes("2+2=4"), [
+            p(NUM), p(&[1, 2]), p(&[2, 3, 5]), p(NUM), p(&[1, 2]), p(&[2, 3, 5, 6]), p(NUM), p(&[1, 4, 5]),
+        ]);
+        // Número romano: doble prefijo de mayúscula
+        assert_eq!(bytes("siglo XX")[6..], [p(&[4, 6]), p(&[4, 6]), p(&[1, 3, 4, 6]), p(&[1, 3, 4, 6])]);
+    }
+}
@@ -0,0 +1,350 @@
+// ─── Paquete EPUB: container.xml y content.opf ───────────────────────────────
+// Lectura con un parser XML de verdad: da igual que los atributos usen comillas
+// simples, que ocupen varias líneas, que el OPF venga minificado en una sola
+// línea o que los elementos lleven prefijo de espacio de nombres (opf:item,
+// dc:title…). Sirve para paquetes EPUB 2 y EPUB 3.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::{ErrorBraille, Metadatos};
+
+const MEDIA_TYPE_OPF: &str = "application/oebps-package+xml";
+
+/// Entrada del `<manifest>`. `href` ya viene resuelto a ruta dentro del zip.
+#[derive(Clone, Debug)]
+pub struct ItemManifiesto {
+    pub id: String,
+    pub href: String,
+    pub media_type: String,
+    /// Atributo `properties` de EPUB 3 (`nav`, `cover-image`…).
+    pub propiedades: Vec<String>,
+}
+
+/// Entrada del `<guide>` de EPUB 2.
+#[derive(Clone, Debug)]
+pub struct Referencia {
+    pub tipo: String,
+    pub titulo: String,
+    pub href: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Paquete {
+    /// Atributo `version` del `<package>` ("2.0", "3.0"…).
+    pub version: String,
+    pub metadatos: Metadatos,
+    pub manifiesto: Vec<ItemManifiesto>,
+    /// `idref` de cada `<itemref>` del spine, en orden de lectura.
+    pub spine: Vec<String>,
+    pub guia: Vec<Referencia>,
+}
+
+impl Paquete {
+    pub fn desde_xml(xml: &str, opf_path: &str) -> Result<Paquete, ErrorBraille> {
+        let base = opf_path.rfind('/').map(|i| &opf_path[..i]).unwrap_or("");
+        let mut paquete = Paquete::default();
+        let mut id_unico = String::new();
+        let mut identificadores: Vec<(String, String)> = Vec::new();
+
+        // Elemento de metadatos Dublin Core abierto: (nombre local, id, texto)
+        let mut dc_abierto: Option<(String, String, String)> = None;
+
+        let mut reader = Reader::from_str(xml);
+        loop {
+            let evento = reader.read_event()
+                .map_err(|e| ErrorBraille::OpfInvalido {
+                    ruta: opf_path.to_string(),
+                    detalle: e.to_string(),
+                })?;
+            match evento {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let nombre = nombre_local(e);
+                    match nombre.as_str() {
+                        "package" => {
+                            paquete.version = atributo(e, "version").unwrap_or_default();
+                            id_unico = atributo(e, "unique-identifier").unwrap_or_default();
+                        }
+                        "title" | "creator" | "language" | "identifier"
+                            if matches!(evento, Event::Start(_)) =>
+                        {
+                            let id = atributo(e, "id").unwrap_or_default();
+                            dc_abierto = Some((nombre, id, String::new()));
+                        }
+                        "item" => {
+                            let id = atributo(e, "id").unwrap_or_default();
+                            let href = atributo(e, "href").unwrap_or_default();
+                            if !id.is_empty() && !href.is_empty() {
+                                paquete.manifiesto.push(ItemManifiesto {
+                                    id,
+                                    href: resolver_ruta(base, &href),
+                                    media_type: atributo(e, "media-type").unwrap_or_default(),
+                                    propiedades: atributo(e, "properties")
+                                        .map(|p| p.split_whitespace().map(String::from).collect())
+                                        .unwrap_or_default(),
+                                });
+                            }
+                        }
+                        "itemref" => {
+                            if let Some(idref) = atributo(e, "idref") {
+                                paquete.spine.push(idref);
+                            }
+                        }
+                        "reference" => {
+                            paquete.guia.push(Referencia {
+                                tipo: atributo(e, "type").unwrap_or_default(),
+                                titulo: atributo(e, "title").unwrap_or_default(),
+                                href: resolver_ruta(base, &atributo(e, "href").unwrap_or_default()),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(ref t) => {
+                    if let Some((_, _, texto)) = dc_abierto.as_mut() {
+                        texto.push_str(&t.xml_content().unwrap_or_default());
+                    }
+                }
+                Event::CData(ref t) => {
+                    if let Some((_, _, texto)) = dc_abierto.as_mut() {
+                        texto.push_str(&t.decode().unwrap_or_default());
+                    }
+                }
+                Event::GeneralRef(ref r) => {
+                    if let Some((_, _, texto)) = dc_abierto.as_mut() {
+                        texto.push_str(&resolver_entidad(&r.decode().unwrap_or_default()));
+                    }
+                }
+                Event::End(_) => {
+                    if let Some((nombre, id, texto)) = dc_abierto.take() {
+                        let texto = texto.split_whitespace().collect::<Vec<_>>().join(" ");
+                        let m = &mut paquete.metadatos;
+                        match nombre.as_str() {
+                            "title" if m.titulo.is_empty() => m.titulo = texto,
+                            "creator" if m.autor.is_empty() => m.autor = texto,
+                            "language" if m.idioma.is_empty() => m.idioma = texto,
+                            "identifier" => identificadores.push((id, texto)),
+                            _ => {}
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        // El identificador del libro es el que señala `unique-identifier`
+        paquete.metadatos.identificador = identificadores.iter()
+            .find(|(id, _)| !id_unico.is_empty() && *id == id_unico)
+            .or_else(|| identificadores.first())
+            .map(|(_, texto)| texto.clone())
+            .unwrap_or_default();
+
+        Ok(paquete)
+    }
+
+    /// Rutas dentro del zip de los documentos XHTML del spine, en orden de lectura.
+    pub fn documentos_spine(&self) -> Vec<String> {
+        self.spine.iter()
+            .filter_map(|idref| self.manifiesto.iter().find(|item| item.id == *idref))
+            .filter(|item| {
+                item.media_type.starts_with("application/xhtml")
+                    || item.media_type == "text/html"
+            })
+            .map(|item| item.href.clone())
+            .collect()
+    }
+
+    /// Rutas dentro del zip de las hojas de estilo del manifiesto.
+    pub fn hojas_de_estilo(&self) -> Vec<String> {
+        self.manifiesto.iter()
+            .filter(|item| item.media_type == "text/css")
+            .map(|item| item.href.clone())
+            .collect()
+    }
+}
+
+/// Ruta del content.opf declarada en META-INF/container.xml.
+pub fn leer_container(xml: &str) -> Result<String, ErrorBraille> {
+    let mut reader = Reader::from_str(xml);
+    let mut primera: Option<String> = None;
+    loop {
+        let evento = reader.read_event()
+            .map_err(|e| ErrorBraille::ContainerInvalido(e.to_string()))?;
+        match evento {
+            Event::Start(ref e) | Event::Empty(ref e) if nombre_local(e) == "rootfile" => {
+                if let Some(ruta) = atributo(e, "full-path") {
+                    // Con varias renditions, la primera de tipo OPF
+                    if atributo(e, "media-type").as_deref() == Some(MEDIA_TYPE_OPF) {
+                        return Ok(ruta);
+                    }
+                    primera.get_or_insert(ruta);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    primera.ok_or_else(|| ErrorBraille::ContainerInvalido("falta el atributo full-path".to_string()))
+}
+
+fn nombre_local(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+/// Valor de un atributo por nombre local, con las entidades ya resueltas.
+fn atributo(e: &BytesStart, nombre: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == nombre.as_bytes())
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+fn resolver_entidad(nombre: &str) -> String {
+    let referencia = format!("&{};", nombre);
+    quick_xml::escape::unescape(&referencia)
+        .map(|t| t.into_owned())
+        .unwrap_or(referencia)
+}
+
+/// Resuelve `href` (relativo al OPF, con posibles `%XX` y `../`) a una ruta
+/// dentro del zip, sin el fragmento.
+fn resolver_ruta(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let href = decodificar_porcentaje(href);
+
+    let mut partes: Vec<&str> = if href.starts_with('/') || base.is_empty() {
+        Vec::new()
+    } else {
+        base.split('/').collect()
+    };
+    for parte in href.split('/') {
+        match parte {
+            "" | "." => {}
+            ".." => {
+                partes.pop();
+            }
+            _ => partes.push(parte),
+        }
+    }
+    partes.join("/")
+}
+
+fn decodificar_porcentaje(texto: &str) -> String {
+    let bytes = texto.as_bytes();
+    let mut salida = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok());
+            if let Some(b) = hex {
+                salida.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        salida.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(salida).unwrap_or_else(|_| texto.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="otro/libro.pdf" media-type="application/pdf"/>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+
+    #[test]
+    fn container_con_varias_renditions() {
+        assert_eq!(leer_container(CONTAINER).unwrap(), "OEBPS/content.opf");
+    }
+
+    #[test]
+    fn container_sin_rootfile() {
+        let xml = r#"<container xmlns="urn:oasis:names:tc:opendocument:xmlns:container"><rootfiles/></container>"#;
+        assert!(matches!(leer_container(xml), Err(ErrorBraille::ContainerInvalido(_))));
+        let sin_ruta = r#"<container><rootfiles><rootfile media-type="application/oebps-package+xml"/></rootfiles></container>"#;
+        assert!(matches!(leer_container(sin_ruta), Err(ErrorBraille::ContainerInvalido(_))));
+    }
+
+    #[test]
+    fn opf_con_prefijos_de_espacio_de_nombres() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opf:package xmlns:opf="http://www.idpf.org/2007/opf" xmlns:dc="http://purl.org/dc/elements/1.1/"
+    version='2.0' unique-identifier='uid'>
+  <opf:metadata>
+    <dc:identifier id="isbn">978-84</dc:identifier>
+    <dc:identifier id='uid'>urn:uuid:1234</dc:identifier>
+    <dc:title>Rico   &amp; famoso</dc:title>
+    <dc:creator opf:role="aut">Ana Pérez</dc:creator>
+    <dc:language>es</dc:language>
+  </opf:metadata>
+  <opf:manifest>
+    <opf:item id="c1" href="Text/cap1.xhtml" media-type="application/xhtml+xml"/>
+    <opf:item id="css" href="estilo.css" media-type="text/css"/>
+  </opf:manifest>
+  <opf:spine toc="ncx"><opf:itemref idref="c1"/><opf:itemref idref="css"/></opf:spine>
+  <opf:guide><opf:reference type="text" title="Inicio" href="Text/cap1.xhtml#inicio"/></opf:guide>
+</opf:package>"#;
+        let paquete = Paquete::desde_xml(xml, "OEBPS/content.opf").unwrap();
+        assert_eq!(paquete.version, "2.0");
+        assert_eq!(paquete.metadatos.titulo, "Rico & famoso");
+        assert_eq!(paquete.metadatos.autor, "Ana Pérez");
+        assert_eq!(paquete.metadatos.idioma, "es");
+        assert_eq!(paquete.metadatos.identificador, "urn:uuid:1234");
+        assert_eq!(paquete.manifiesto.len(), 2);
+        assert_eq!(paquete.spine, ["c1", "css"]);
+        assert_eq!(paquete.guia[0].href, "OEBPS/Text/cap1.xhtml");
+        // La hoja de estilo del spine no es un documento
+        assert_eq!(paquete.documentos_spine(), ["OEBPS/Text/cap1.xhtml"]);
+    }
+
+    #[test]
+    fn spine_con_idref_sin_manifiesto() {
+        let xml = r#"<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <manifest>
+    <item id="a" href="a.xhtml" media-type="application/xhtml+xml" properties="nav scripted"/>
+    <item id="b" href="b.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine><itemref idref="a"/><itemref idref="falta"/><itemref idref="b"/></spine>
+</package>"#;
+        let paquete = Paquete::desde_xml(xml, "content.opf").unwrap();
+        assert_eq!(paquete.manifiesto[0].propiedades, ["nav", "scripted"]);
+        assert_eq!(paquete.documentos_spine(), ["a.xhtml", "b.xhtml"]);
+    }
+
+    #[test]
+    fn opf_mal_formado() {
+        let error = Paquete::desde_xml("<package><manifest></package>", "content.opf");
+        assert!(matches!(error, Err(ErrorBraille::OpfInvalido { .. })));
+    }
+
+    #[test]
+    fn rutas() {
+        assert_eq!(resolver_ruta("OEBPS", "Text/Cap%C3%ADtulo%201.xhtml"), "OEBPS/Text/Capítulo 1.xhtml");
+        assert_eq!(resolver_ruta("OEBPS/Text", "../Images/tapa.jpg"), "OEBPS/Images/tapa.jpg");
+        assert_eq!(resolver_ruta("OEBPS", "./cap.xhtml#nota-3"), "OEBPS/cap.xhtml");
+        assert_eq!(resolver_ruta("", "cap.xhtml"), "cap.xhtml");
+        assert_eq!(resolver_ruta("OEBPS", "/raiz.xhtml"), "raiz.xhtml");
+    }
+
+    #[test]
+    fn porcentajes() {
+        assert_eq!(decodificar_porcentaje("a%20b"), "a b");
+        assert_eq!(decodificar_porcentaje("%C3%B1and%C3%BA"), "ñandú");
+        // Secuencias incompletas o no hexadecimales se dejan como vienen
+        assert_eq!(decodificar_porcentaje("100%"), "100%");
+        assert_eq!(decodificar_porcentaje("%2"), "%2");
+        assert_eq!(decodificar_porcentaje("%zz"), "%zz");
+        // Bytes que no forman UTF-8: el texto original
+        assert_eq!(decodificar_porcentaje("%FF"), "%FF");
+    }
+}
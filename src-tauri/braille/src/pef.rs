@@ -0,0 +1,131 @@
+// ─── PEF: Portable Embosser Format (2008-1) ──────────────────────────────────
+// XML normalizado para archivo y emboss: volumen → sección → página → fila,
+// con el texto de cada fila en braille Unicode y metadatos Dublin Core.
+
+use super::{linea_a_unicode, Maquetacion, Metadatos, Pagina};
+
+const NS_PEF: &str = "http://www.daisy.org/ns/2008/pef";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+
+fn escapar_xml(texto: &str) -> String {
+    texto.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn elemento_dc(pef: &mut String, nombre: &str, valor: &str) {
+    if !valor.is_empty() {
+        pef.push_str(&format!("      <dc:{0}>{1}</dc:{0}>\n", nombre, escapar_xml(valor)));
+    }
+}
+
+/// Genera un documento PEF de un solo volumen y una sola sección.
+/// Las celdas en blanco del final de cada fila se omiten, como permite el estándar.
+pub fn paginas_a_pef(
+    paginas: &[Pagina],
+    maquetacion: &Maquetacion,
+    metadatos: &Metadatos,
+) -> String {
+    // dc:identifier es obligatorio en PEF
+    let identificador = if !metadatos.identificador.is_empty() {
+        metadatos.identificador.as_str()
+    } else if !metadatos.titulo.is_empty() {
+        metadatos.titulo.as_str()
+    } else {
+        "epub2braille"
+    };
+
+    let mut pef = String::new();
+    pef.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    pef.push_str(&format!("<pef version=\"2008-1\" xmlns=\"{}\">\n", NS_PEF));
+    pef.push_str("  <head>\n");
+    pef.push_str(&format!("    <meta xmlns:dc=\"{}\">\n", NS_DC));
+    elemento_dc(&mut pef, "format", "application/x-pef+xml");
+    elemento_dc(&mut pef, "identifier", identificador);
+    elemento_dc(&mut pef, "title", &metadatos.titulo);
+    elemento_dc(&mut pef, "creator", &metadatos.autor);
+    elemento_dc(&mut pef, "language", &metadatos.idioma);
+    pef.push_str("    </meta>\n");
+    pef.push_str("  </head>\n");
+    pef.push_str("  <body>\n");
+    pef.push_str(&format!(
+        "    <volume cols=\"{}\" rows=\"{}\" rowgap=\"0\" duplex=\"false\">\n",
+        maquetacion.celdas_por_linea, maquetacion.lineas_por_pagina,
+    ));
+    pef.push_str("      <section>\n");
+
+    for pagina in paginas {
+        pef.push_str("        <page>\n");
+        for linea in &pagina.lineas {
+            let fila = linea_a_unicode(linea);
+            let fila = fila.trim_end_matches('\u{2800}');
+            if fila.is_empty() {
+                pef.push_str("          <row/>\n");
+            } else {
+                pef.push_str(&format!("          <row>{}</row>\n", fila));
+            }
+        }
+        pef.push_str("        </page>\n");
+    }
+
+    pef.push_str("      </section>\n");
+    pef.push_str("    </volume>\n");
+    pef.push_str("  </body>\n");
+    pef.push_str("</pef>\n");
+    pef
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puntos_a_byte;
+
+    fn ejemplo(metadatos: &Metadatos) -> String {
+        let maquetacion = Maquetacion { celdas_por_linea: 4, lineas_por_pagina: 2, ..Maquetacion::default() };
+        let a = puntos_a_byte(&[1]);
+        let numero = puntos_a_byte(&[3, 4, 5, 6]);
+        let paginas = [
+            Pagina { numero: 1, lineas: vec![vec![a, 0, a, 0], vec![0; 4]] },
+            Pagina { numero: 2, lineas: vec![vec![numero, a, 0, 0], vec![0; 4]] },
+        ];
+        paginas_a_pef(&paginas, &maquetacion, metadatos)
+    }
+
+    #[test]
+    fn volumen_seccion_pagina_fila() {
+        let pef = ejemplo(&Metadatos::default());
+        assert!(pef.contains(r#"<volume cols="4" rows="2" rowgap="0" duplex="false">"#), "{}", pef);
+        assert_eq!(pef.matches("<volume ").count(), 1);
+        assert_eq!(pef.matches("<section>").count(), 1);
+        assert_eq!(pef.matches("<page>").count(), 2);
+        // Filas en braille Unicode, sin las celdas en blanco del final
+        let cuerpo = &pef[pef.find("<section>").unwrap()..];
+        assert_eq!(cuerpo.lines().map(str::trim).collect::<Vec<_>>(), [
+            "<section>",
+            "<page>", "<row>⠁⠀⠁</row>", "<row/>", "</page>",
+            "<page>", "<row>⠼⠁</row>", "<row/>", "</page>",
+            "</section>", "</volume>", "</body>", "</pef>",
+        ]);
+    }
+
+    #[test]
+    fn metadatos_dublin_core() {
+        let pef = ejemplo(&Metadatos {
+            titulo: "Tom & Jerry <2>".to_string(),
+            autor: "Ana \"la Roja\"".to_string(),
+            idioma: "es".to_string(),
+            identificador: "urn:isbn:123".to_string(),
+        });
+        assert!(pef.contains("<dc:title>Tom &amp; Jerry &lt;2&gt;</dc:title>"), "{}", pef);
+        assert!(pef.contains("<dc:creator>Ana &quot;la Roja&quot;</dc:creator>"));
+        assert!(pef.contains("<dc:language>es</dc:language>"));
+        assert!(pef.contains("<dc:identifier>urn:isbn:123</dc:identifier>"));
+
+        // Sin idioma no hay dc:language; el identificador, obligatorio, sale del título
+        let pef = ejemplo(&Metadatos { titulo: "Rayuela".to_string(), ..Metadatos::default() });
+        assert!(!pef.contains("dc:language"));
+        assert!(!pef.contains("dc:creator"));
+        assert!(pef.contains("<dc:identifier>Rayuela</dc:identifier>"));
+    }
+}
@@ -0,0 +1,442 @@
+// ─── Resalte: cursiva, negrita y subrayado ───────────────────────────────────
+// El recorrido del XHTML deja en el texto del bloque una marca (un carácter de
+// uso privado) al abrir y al cerrar cada resalte, igual que deja U+2028 por
+// cada <br>. El codificador quita las marcas y escribe los indicadores de la
+// CBE: el de palabra si el resalte abarca una sola, o los de apertura y cierre
+// de pasaje si abarca varias. Los caracteres de uso privado U+E000–U+E005 que
+// traiga el propio libro (fuentes de iconos) o el texto del editor se quitan
+// al extraerlo, antes de que se inserte ninguna marca.
+
+use serde::{Deserialize, Serialize};
+
+/// Clase de resalte, según la etiqueta, el estilo o la clase CSS que lo produjo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resalte {
+    /// `<em>`, `<i>`, `font-style: italic`.
+    Cursiva,
+    /// `<strong>`, `<b>`, `font-weight: bold`.
+    Negrita,
+    /// `<u>`, `<ins>`, `text-decoration: underline`.
+    Subrayado,
+}
+
+impl Resalte {
+    pub const TODOS: [Resalte; 3] = [Resalte::Cursiva, Resalte::Negrita, Resalte::Subrayado];
+
+    pub fn apertura(self) -> char {
+        match self {
+            Resalte::Cursiva => '\u{E000}',
+            Resalte::Negrita => '\u{E002}',
+            Resalte::Subrayado => '\u{E004}',
+        }
+    }
+
+    pub fn cierre(self) -> char {
+        match self {
+            Resalte::Cursiva => '\u{E001}',
+            Resalte::Negrita => '\u{E003}',
+            Resalte::Subrayado => '\u{E005}',
+        }
+    }
+
+    /// Resalte de una etiqueta en línea.
+    pub fn de_etiqueta(nombre: &str) -> Option<Resalte> {
+        match nombre {
+            "em" | "i" => Some(Resalte::Cursiva),
+            "strong" | "b" => Some(Resalte::Negrita),
+            "u" | "ins" => Some(Resalte::Subrayado),
+            _ => None,
+        }
+    }
+
+    /// Resaltes de un atributo `style` ("font-style: italic; font-weight: 700").
+    pub fn de_estilo(estilo: &str) -> Vec<Resalte> {
+        let mut resaltes = Vec::new();
+        for declaracion in estilo.split(';') {
+            let Some((propiedad, valor)) = declaracion.split_once(':') else {
+                continue;
+            };
+            let valor = valor.trim().to_ascii_lowercase();
+            let resalte = match propiedad.trim().to_ascii_lowercase().as_str() {
+                "font-style" if valor.starts_with("italic") || valor.starts_with("oblique") => {
+                    Resalte::Cursiva
+                }
+                "font-weight" if valor == "bold" || valor == "bolder"
+                    || valor.parse::<u32>().is_ok_and(|peso| peso >= 600) => Resalte::Negrita,
+                "text-decoration" | "text-decoration-line" if valor.contains("underline") => {
+                    Resalte::Subrayado
+                }
+                _ => continue,
+            };
+            if !resaltes.contains(&resalte) {
+                resaltes.push(resalte);
+            }
+        }
+        resaltes
+    }
+
+    pub(crate) fn indice(self) -> usize {
+        self as usize
+    }
+
+    /// Resalte que abre (true) o cierra (false) la marca.
+    fn de_marca(c: char) -> Option<(Resalte, bool)> {
+        Resalte::TODOS.into_iter().find_map(|r| {
+            if c == r.apertura() {
+                Some((r, true))
+            } else if c == r.cierre() {
+                Some((r, false))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// ─── Clases de las hojas de estilo del libro ────────────────────────────────
+// Los EPUB suelen marcar la cursiva con una clase (<span class="italic">) y
+// una regla en su hoja de estilo. Se leen las reglas de selectores de una
+// sola clase, con o sin etiqueta (".italic", "span.nota"); los combinadores,
+// las pseudoclases y los identificadores se ignoran, y las reglas de @media
+// cuentan como si no lo fueran.
+
+/// Resaltes que las hojas de estilo asignan a cada clase.
+#[derive(Clone, Debug, Default)]
+pub struct EstilosCss {
+    reglas: Vec<ReglaClase>,
+}
+
+#[derive(Clone, Debug)]
+struct ReglaClase {
+    /// `None` si el selector no nombra etiqueta.
+    etiqueta: Option<String>,
+    clase: String,
+    resaltes: Vec<Resalte>,
+}
+
+impl EstilosCss {
+    /// Añade las reglas de una hoja de estilo (un .css o un `<style>`).
+    pub fn anadir(&mut self, css: &str) {
+        for regla in quitar_comentarios(css).split('}') {
+            let Some((selectores, declaraciones)) = regla.rsplit_once('{') else {
+                continue;
+            };
+            let resaltes = Resalte::de_estilo(declaraciones);
+            if resaltes.is_empty() {
+                continue;
+            }
+            // Dentro de @media, el selector es lo que sigue a la última llave
+            let selectores = selectores.rsplit('{').next().unwrap_or(selectores);
+            for selector in selectores.split(',') {
+                if let Some((etiqueta, clase)) = selector_de_clase(selector.trim()) {
+                    self.reglas.push(ReglaClase { etiqueta, clase, resaltes: resaltes.clone() });
+                }
+            }
+        }
+    }
+
+    /// Resaltes de un elemento por su etiqueta y su atributo `class`.
+    pub fn de_clases(&self, etiqueta: &str, clases: &str) -> Vec<Resalte> {
+        let mut resaltes = Vec::new();
+        for regla in &self.reglas {
+            let aplica = clases.split_whitespace().any(|c| c == regla.clase)
+                && regla.etiqueta.as_deref().is_none_or(|e| e.eq_ignore_ascii_case(etiqueta));
+            if aplica {
+                for &r in &regla.resaltes {
+                    if !resaltes.contains(&r) {
+                        resaltes.push(r);
+                    }
+                }
+            }
+        }
+        resaltes
+    }
+}
+
+fn quitar_comentarios(css: &str) -> String {
+    let mut limpio = String::with_capacity(css.len());
+    let mut resto = css;
+    while let Some(inicio) = resto.find("/*") {
+        limpio.push_str(&resto[..inicio]);
+        resto = resto[inicio + 2..].find("*/").map_or("", |fin| &resto[inicio + 2 + fin + 2..]);
+    }
+    limpio.push_str(resto);
+    limpio
+}
+
+/// Etiqueta y clase de un selector "etiqueta.clase" o ".clase".
+fn selector_de_clase(selector: &str) -> Option<(Option<String>, String)> {
+    let (etiqueta, clase) = selector.split_once('.')?;
+    let nombre = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !nombre(clase) || !(etiqueta.is_empty() || nombre(etiqueta)) {
+        return None;
+    }
+    let etiqueta = (!etiqueta.is_empty()).then(|| etiqueta.to_string());
+    Some((etiqueta, clase.to_string()))
+}
+
+pub fn es_marca(c: char) -> bool {
+    Resalte::de_marca(c).is_some()
+}
+
+/// El texto sin las marcas de resalte.
+pub fn quitar_marcas(texto: &str) -> String {
+    texto.chars().filter(|&c| !es_marca(c)).collect()
+}
+
+/// Las marcas de apertura pasan por delante del espacio que las sigue y las
+/// de cierre por detrás del que las precede: "<i> dos palabras </i>" resalta
+/// las palabras, no los espacios.
+pub fn cenir_marcas(texto: &str) -> String {
+    let mut chars: Vec<char> = texto.chars().collect();
+    let mut cambio = true;
+    while cambio {
+        cambio = false;
+        for k in 0..chars.len().saturating_sub(1) {
+            let (a, b) = (chars[k], chars[k + 1]);
+            let abre = Resalte::de_marca(a).is_some_and(|(_, abre)| abre) && b.is_whitespace();
+            let cierra = a.is_whitespace() && Resalte::de_marca(b).is_some_and(|(_, abre)| !abre);
+            if abre || cierra {
+                chars.swap(k, k + 1);
+                cambio = true;
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Qué resaltes del libro se indican en braille.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Enfasis {
+    pub cursiva: bool,
+    pub negrita: bool,
+    pub subrayado: bool,
+}
+
+impl Default for Enfasis {
+    fn default() -> Self {
+        Enfasis { cursiva: true, negrita: true, subrayado: true }
+    }
+}
+
+impl Enfasis {
+    /// Sin ningún indicador de resalte.
+    pub fn ninguno() -> Self {
+        Enfasis { cursiva: false, negrita: false, subrayado: false }
+    }
+
+    pub fn cuenta(&self, resalte: Resalte) -> bool {
+        match resalte {
+            Resalte::Cursiva => self.cursiva,
+            Resalte::Negrita => self.negrita,
+            Resalte::Subrayado => self.subrayado,
+        }
+    }
+}
+
+/// Tramo resaltado del texto ya sin marcas, en caracteres, ajustado a
+/// palabras enteras.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Tramo {
+    pub inicio: usize,
+    pub fin: usize,
+    /// Varias palabras: indicadores de apertura y cierre de pasaje.
+    pub pasaje: bool,
+}
+
+/// Quita las marcas y devuelve los tramos resaltados que cuentan según
+/// `enfasis`. Los resaltes de clases distintas que se solapan o que solo
+/// separa un espacio forman un único tramo: la CBE no los distingue.
+pub(crate) fn separar(texto: &[char], enfasis: &Enfasis) -> (Vec<char>, Vec<Tramo>) {
+    let mut limpio = Vec::with_capacity(texto.len());
+    let mut resaltado = Vec::with_capacity(texto.len());
+    let mut abiertos = [0usize; 3];
+    for &c in texto {
+        match Resalte::de_marca(c) {
+            Some((r, true)) => abiertos[r.indice()] += 1,
+            Some((r, false)) => abiertos[r.indice()] = abiertos[r.indice()].saturating_sub(1),
+            None => {
+                limpio.push(c);
+                resaltado.push(Resalte::TODOS.iter().any(|&r| abiertos[r.indice()] > 0 && enfasis.cuenta(r)));
+            }
+        }
+    }
+
+    let mut tramos: Vec<Tramo> = Vec::new();
+    let mut k = 0;
+    while k < limpio.len() {
+        if !resaltado[k] {
+            k += 1;
+            continue;
+        }
+        let mut inicio = k;
+        let mut fin = k;
+        while fin < limpio.len() && resaltado[fin] {
+            fin += 1;
+        }
+        k = fin;
+
+        // Sin los espacios de los extremos, y en palabras enteras
+        while inicio < fin && limpio[inicio].is_whitespace() {
+            inicio += 1;
+        }
+        while fin > inicio && limpio[fin - 1].is_whitespace() {
+            fin -= 1;
+        }
+        if inicio == fin {
+            continue;
+        }
+        while inicio > 0 && limpio[inicio - 1].is_alphanumeric() {
+            inicio -= 1;
+        }
+        while fin < limpio.len() && limpio[fin].is_alphanumeric() {
+            fin += 1;
+        }
+
+        match tramos.last_mut() {
+            Some(anterior) if limpio[anterior.fin.min(inicio)..inicio].iter().all(|&c| c == ' ') => {
+                anterior.fin = anterior.fin.max(fin);
+            }
+            _ => tramos.push(Tramo { inicio, fin, pasaje: false }),
+        }
+    }
+    for tramo in &mut tramos {
+        tramo.pasaje = limpio[tramo.inicio..tramo.fin].iter().any(|c| c.is_whitespace());
+    }
+    (limpio, tramos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codificar_documento, puntos_a_byte, Documento, OpcionesCodificacion, TablaEspanol,
+    };
+
+    fn marcado(texto: &str) -> Vec<char> {
+        // _ abre y cierra cursiva, * negrita
+        let mut cursiva = false;
+        let mut negrita = false;
+        texto.chars().map(|c| match c {
+            '_' => {
+                cursiva = !cursiva;
+                if cursiva { Resalte::Cursiva.apertura() } else { Resalte::Cursiva.cierre() }
+            }
+            '*' => {
+                negrita = !negrita;
+                if negrita { Resalte::Negrita.apertura() } else { Resalte::Negrita.cierre() }
+            }
+            c => c,
+        }).collect()
+    }
+
+    fn tramos(texto: &str, enfasis: &Enfasis) -> Vec<(String, bool)> {
+        let (limpio, tramos) = separar(&marcado(texto), enfasis);
+        tramos.iter()
+            .map(|t| (limpio[t.inicio..t.fin].iter().collect(), t.pasaje))
+            .collect()
+    }
+
+    #[test]
+    fn tramos_de_palabra_y_de_pasaje() {
+        let todos = Enfasis::default();
+        assert_eq!(tramos("leí _Rayuela_ ayer", &todos), [("Rayuela".to_string(), false)]);
+        assert_eq!(tramos("_Cien años de soledad_.", &todos), [("Cien años de soledad".to_string(), true)]);
+        // Un trozo de palabra resalta la palabra entera
+        assert_eq!(tramos("super_mer_cado", &todos), [("supermercado".to_string(), false)]);
+        // Los espacios de los extremos no cuentan
+        assert_eq!(tramos("a_ b _c", &todos), [("b".to_string(), false)]);
+        // Clases distintas seguidas forman un pasaje
+        assert_eq!(tramos("_muy_ *importante* hoy", &todos), [("muy importante".to_string(), true)]);
+        assert!(tramos("sin resalte", &todos).is_empty());
+    }
+
+    #[test]
+    fn solo_las_clases_elegidas() {
+        let solo_cursiva = Enfasis { negrita: false, ..Enfasis::default() };
+        assert_eq!(tramos("_uno_ y *dos*", &solo_cursiva), [("uno".to_string(), false)]);
+        assert!(tramos("_uno_ y *dos*", &Enfasis::ninguno()).is_empty());
+    }
+
+    #[test]
+    fn estilos_css() {
+        assert_eq!(Resalte::de_estilo("font-style: italic"), [Resalte::Cursiva]);
+        assert_eq!(Resalte::de_estilo("FONT-WEIGHT:700; text-decoration: underline overline"),
+                   [Resalte::Negrita, Resalte::Subrayado]);
+        assert!(Resalte::de_estilo("font-weight: 400; color: red").is_empty());
+    }
+
+    #[test]
+    fn clases_de_la_hoja_de_estilo() {
+        let mut estilos = EstilosCss::default();
+        estilos.anadir("/* .comentada { font-style: italic } */
+            .italic, .cursiva { font-style: italic }
+            span.b { font-weight: bold; }
+            p > .hija { font-style: italic }
+            .normal { font-style: normal }
+            @media print { .sub { text-decoration: underline } }");
+        assert_eq!(estilos.de_clases("span", "nota italic"), [Resalte::Cursiva]);
+        assert_eq!(estilos.de_clases("em", "cursiva b"), [Resalte::Cursiva]);
+        assert_eq!(estilos.de_clases("span", "b italic"), [Resalte::Cursiva, Resalte::Negrita]);
+        assert_eq!(estilos.de_clases("span", "sub"), [Resalte::Subrayado]);
+        assert!(estilos.de_clases("span", "comentada hija normal").is_empty());
+        assert!(estilos.de_clases("span", "italico").is_empty());
+
+        // También los <style> del propio capítulo
+        let documento = Documento::desde_xhtml(
+            "<html><head><style>.n { font-weight: 700 }</style></head>\
+             <body><p>uno <span class='n'>dos</span></p></body></html>");
+        let (_, tramos) = separar(&documento.bloques[0].texto.chars().collect::<Vec<_>>(), &Enfasis::default());
+        assert_eq!(tramos, [Tramo { inicio: 4, fin: 7, pasaje: false }]);
+    }
+
+    #[test]
+    fn desde_el_xhtml() {
+        let documento = Documento::desde_xhtml(
+            "<body><p>Leí <i>Rayuela</i> y <span style='font-weight:bold'> la <b>otra</b> novela</span>.</p>\
+             <em><p>uno</p><p>dos</p></em></body>");
+        let textos: Vec<String> = documento.bloques.iter()
+            .map(|b| b.texto.chars().map(|c| match Resalte::de_marca(c) {
+                Some((_, true)) => '[',
+                Some((_, false)) => ']',
+                None => c,
+            }).collect())
+            .collect();
+        // <b> dentro de un estilo en negrita no abre otro resalte
+        assert_eq!(textos, ["Leí [Rayuela] y [la otra novela].", "[uno]", "[dos]"]);
+        // El editor recibe el texto sin marcas
+        assert_eq!(documento.a_texto(), "Leí Rayuela y la otra novela.\nuno\ndos\n");
+    }
+
+    #[test]
+    fn caracteres_de_uso_privado_del_libro() {
+        let documento = Documento::desde_xhtml("<body><p>\u{E000}icono\u{E001} <i>sí</i>\u{E005}</p></body>");
+        let (limpio, tramos) = separar(&documento.bloques[0].texto.chars().collect::<Vec<_>>(), &Enfasis::default());
+        assert_eq!(limpio.iter().collect::<String>(), "icono sí");
+        assert_eq!(tramos, [Tramo { inicio: 6, fin: 8, pasaje: false }]);
+
+        let documento = Documento::desde_texto("\u{E002}icono\u{E003} y más");
+        assert_eq!(documento.bloques[0].texto, "icono y más");
+    }
+
+    #[test]
+    fn indicadores() {
+        let bytes = |xhtml: &str, enfasis: Enfasis| -> Vec<u8> {
+            let opciones = OpcionesCodificacion { enfasis, ..OpcionesCodificacion::default() };
+            let documento = Documento::desde_xhtml(xhtml);
+            let (celdas, _) = codificar_documento(&documento, &TablaEspanol, &opciones);
+            celdas.iter().filter(|c| c.salto.is_none()).map(|c| c.byte).collect()
+        };
+        let p = puntos_a_byte;
+        let palabra = [p(&[4, 5, 6]), p(&[2])];
+        let abre = [p(&[4, 5, 6]), p(&[2, 3, 5, 6])];
+        let cierra = [p(&[4, 5, 6]), p(&[3])];
+        let (a, b) = (p(&[1]), p(&[1, 2]));
+
+        assert_eq!(bytes("<p><i>a</i> b</p>", Enfasis::default()), [&palabra[..], &[a, 0, b]].concat());
+        assert_eq!(bytes("<p><b>a b</b>.</p>", Enfasis::default()),
+                   [&abre[..], &[a, 0, b], &cierra, &[p(&[3])]].concat());
+        assert_eq!(bytes("<p><i>a</i> b</p>", Enfasis::ninguno()), [a, 0, b]);
+    }
+}
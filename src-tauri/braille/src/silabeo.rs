@@ -0,0 +1,394 @@
+// ─── Silabador español ────────────────────────────────────────────────────────
+// Implementación de las reglas fonéticas de la RAE / CBE (sin dependencias externas).
+// Las letras cuentan por cómo suenan: la u de que, qui, gue, gui es muda y va
+// con su consonante (a-quí, se-guí), y la y final o ante consonante es vocal
+// (rey, muy, Ay-ma-ra). El grupo tl se separa en España (at-le-ta) y no en
+// América (a-tle-ta).
+
+use crate::excepciones;
+
+/// Reglas de silabeo de una lengua: qué letras son vocales, cuáles forman
+/// hiato y qué grupos de consonantes no se separan.
+#[derive(Clone, Copy, Debug)]
+pub struct ReglasSilabeo {
+    /// Vocales, en minúscula.
+    pub vocales: &'static str,
+    /// Vocales abiertas: dos seguidas forman hiato.
+    pub fuertes: &'static str,
+    /// Vocales cerradas acentuadas, que rompen el diptongo.
+    pub debiles_acentuadas: &'static str,
+    /// Grupos de dos consonantes que van juntos a la sílaba siguiente.
+    pub inseparables: &'static [&'static str],
+    /// La h entre dos vocales no impide el diptongo (ahu-mar, prohi-bir):
+    /// no se parte ante ella.
+    pub h_intercalada: bool,
+    /// La u de que, qui, gue, gui no suena: no forma diptongo y va con la
+    /// consonante a la sílaba siguiente (a-quí, se-guí).
+    pub u_muda: bool,
+    /// La y tras vocal es vocal si no la sigue otra (rey, muy, Ay-ma-ra);
+    /// ante vocal sigue siendo consonante (re-yes).
+    pub y_vocal: bool,
+}
+
+/// Reglas de la RAE / CBE, con la norma de España: el grupo tl se separa
+/// (at-le-ta).
+pub const SILABEO_ESPANOL: ReglasSilabeo = ReglasSilabeo {
+    vocales: "aeiouáéíóúü",
+    fuertes: "aeoáéó",
+    debiles_acentuadas: "íú",
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr",
+        "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "ch", "ll", "rr",
+    ],
+    h_intercalada: true,
+    u_muda: true,
+    y_vocal: true,
+};
+
+/// Reglas de la RAE / CBE con la norma de México y el resto de América: el
+/// grupo tl no se separa (a-tle-ta, A-tlán-ti-co).
+pub const SILABEO_ESPANOL_AMERICA: ReglasSilabeo = ReglasSilabeo {
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr",
+        "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "ch", "ll", "rr",
+        "tl",
+    ],
+    ..SILABEO_ESPANOL
+};
+
+fn minuscula_simple(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn es_vocal(c: char, reglas: &ReglasSilabeo) -> bool {
+    reglas.vocales.contains(minuscula_simple(c))
+}
+
+fn es_vocal_fuerte(c: char, reglas: &ReglasSilabeo) -> bool {
+    reglas.fuertes.contains(minuscula_simple(c))
+}
+
+fn es_vocal_debil_acentuada(c: char, reglas: &ReglasSilabeo) -> bool {
+    reglas.debiles_acentuadas.contains(minuscula_simple(c))
+}
+
+/// ¿Suena como vocal la letra `k` de la palabra? Tiene en cuenta la u muda
+/// y la y vocal.
+fn suena_vocal(chars: &[char], k: usize, reglas: &ReglasSilabeo) -> bool {
+    let anterior = k.checked_sub(1).map(|a| minuscula_simple(chars[a]));
+    let siguiente = chars.get(k + 1).copied();
+    match minuscula_simple(chars[k]) {
+        'u' if reglas.u_muda => {
+            let muda = matches!(anterior, Some('q' | 'g'))
+                && siguiente.is_some_and(|s| "eiéíêè".contains(minuscula_simple(s)));
+            !muda
+        }
+        'y' if reglas.y_vocal => {
+            anterior.is_some_and(|a| es_vocal(a, reglas))
+                && !siguiente.is_some_and(|s| es_vocal(s, reglas))
+        }
+        c => es_vocal(c, reglas),
+    }
+}
+
+fn forman_diptongo(v1: char, v2: char, reglas: &ReglasSilabeo) -> bool {
+    // Hiato: dos vocales fuertes
+    if es_vocal_fuerte(v1, reglas) && es_vocal_fuerte(v2, reglas) {
+        return false;
+    }
+    // Hiato: vocal débil acentuada (rompe el diptongo)
+    if es_vocal_debil_acentuada(v1, reglas) || es_vocal_debil_acentuada(v2, reglas) {
+        return false;
+    }
+    true
+}
+
+// Grupos consonánticos inseparables (van juntos a la sílaba siguiente). Entre
+// consonantes solo queda la u cuando es muda, y entonces es parte de qu o gu.
+fn es_grupo_inseparable(c1: char, c2: char, reglas: &ReglasSilabeo) -> bool {
+    let (c1, c2) = (minuscula_simple(c1), minuscula_simple(c2));
+    if reglas.u_muda && matches!(c1, 'q' | 'g') && c2 == 'u' {
+        return true;
+    }
+    let s: String = [c1, c2].iter().collect();
+    reglas.inseparables.contains(&s.as_str())
+}
+
+/// Retorna los índices (en bytes) donde comienza cada sílaba dentro de `palabra`.
+/// El primer elemento siempre es 0. Las palabras del diccionario de
+/// excepciones y las que llevan prefijo se parten por su morfología.
+pub fn silabear(palabra: &str) -> Vec<usize> {
+    silabear_espanol(palabra, &SILABEO_ESPANOL)
+}
+
+/// `silabear` con otra variante de las reglas del español
+/// (`SILABEO_ESPANOL_AMERICA`).
+pub fn silabear_espanol(palabra: &str, reglas: &ReglasSilabeo) -> Vec<usize> {
+    let foneticas = |p: &str| silabear_con(p, reglas);
+    excepciones::silabear_espanol(palabra, &foneticas).unwrap_or_else(|| foneticas(palabra))
+}
+
+/// `silabear` con las reglas de otra lengua.
+pub fn silabear_con(palabra: &str, reglas: &ReglasSilabeo) -> Vec<usize> {
+    let chars: Vec<char> = palabra.chars().collect();
+    let n = chars.len();
+    if n == 0 {
+        return vec![];
+    }
+    let es_vocal = |k: usize| suena_vocal(&chars, k, reglas);
+
+    // Construir tabla de índices byte → char
+    let char_to_byte: Vec<usize> = {
+        let mut v = Vec::with_capacity(n + 1);
+        let mut b = 0usize;
+        for &c in &chars {
+            v.push(b);
+            b += c.len_utf8();
+        }
+        v.push(b);
+        v
+    };
+
+    // Índices de inicio de sílaba (en posición de char)
+    let mut cortes_char: Vec<usize> = vec![0]; // siempre empieza en 0
+    let mut i = 0usize;
+
+    while i < n {
+        // Avanzar consonantes iniciales hasta vocal
+        while i < n && !es_vocal(i) {
+            i += 1;
+        }
+        if i >= n { break; }
+
+        // Consumir la vocal (y posible diptongo / triptongo)
+        i += 1;
+        if i < n && es_vocal(i)
+            && forman_diptongo(chars[i - 1], chars[i], reglas)
+        {
+            i += 1;
+            // Triptongo
+            if i < n && es_vocal(i)
+                && forman_diptongo(chars[i - 1], chars[i], reglas)
+            {
+                i += 1;
+            }
+        }
+
+        if i >= n { break; }
+
+        // Contar consonantes hasta la siguiente vocal
+        let cons_start = i;
+        while i < n && !es_vocal(i) {
+            i += 1;
+        }
+        let num_cons = i - cons_start;
+
+        if i >= n {
+            // Final de palabra: no hay más cortes
+            break;
+        }
+
+        if num_cons == 0 {
+            // Hiato: dos vocales que no forman diptongo
+            if !forman_diptongo(chars[i - 1], chars[i], reglas) {
+                cortes_char.push(i);
+            }
+        } else if num_cons == 1 {
+            // Una consonante → va con la sílaba siguiente, salvo una h muda
+            // entre vocales que forman diptongo
+            let h_en_diptongo = reglas.h_intercalada
+                && minuscula_simple(chars[cons_start]) == 'h'
+                && forman_diptongo(chars[cons_start - 1], chars[i], reglas);
+            if !h_en_diptongo {
+                cortes_char.push(cons_start);
+            }
+            i = cons_start + 1;
+        } else if num_cons == 2 {
+            if es_grupo_inseparable(chars[cons_start], chars[cons_start + 1], reglas) {
+                cortes_char.push(cons_start);
+            } else {
+                cortes_char.push(cons_start + 1);
+            }
+            i = cons_start + num_cons;
+        } else if num_cons == 3 {
+            // ¿Las dos últimas forman grupo inseparable?
+            if es_grupo_inseparable(chars[cons_start + 1], chars[cons_start + 2], reglas) {
+                cortes_char.push(cons_start + 1);
+            } else {
+                cortes_char.push(cons_start + 2);
+            }
+            i = cons_start + num_cons;
+        } else {
+            // 4+ consonantes (muy raro): corte a la mitad
+            let mid = num_cons / 2;
+            cortes_char.push(cons_start + mid);
+            i = cons_start + num_cons;
+        }
+    }
+
+    cortes_char.iter().map(|&ci| char_to_byte[ci]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// La palabra con sus sílabas separadas por guiones.
+    fn silabas(palabra: &str, reglas: &ReglasSilabeo) -> String {
+        let cortes = silabear_espanol(palabra, reglas);
+        let mut resultado = String::new();
+        for (k, c) in palabra.char_indices() {
+            if k > 0 && cortes.contains(&k) {
+                resultado.push('-');
+            }
+            resultado.push(c);
+        }
+        resultado
+    }
+
+    fn espana(palabra: &str) -> String {
+        silabas(palabra, &SILABEO_ESPANOL)
+    }
+
+    fn america(palabra: &str) -> String {
+        silabas(palabra, &SILABEO_ESPANOL_AMERICA)
+    }
+
+    #[test]
+    fn grupos_inseparables() {
+        let r = &SILABEO_ESPANOL;
+        for grupo in ["bl", "br", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "pl", "pr", "tr"] {
+            let mut c = grupo.chars();
+            let (c1, c2) = (c.next().unwrap(), c.next().unwrap());
+            assert!(es_grupo_inseparable(c1, c2, r), "{}", grupo);
+            assert!(es_grupo_inseparable(c1.to_ascii_uppercase(), c2.to_ascii_uppercase(), r), "{}", grupo);
+        }
+        // Dígrafos
+        assert!(es_grupo_inseparable('c', 'h', r));
+        assert!(es_grupo_inseparable('l', 'l', r));
+        assert!(es_grupo_inseparable('r', 'r', r));
+        // Consonante + u muda
+        assert!(es_grupo_inseparable('q', 'u', r));
+        assert!(es_grupo_inseparable('g', 'u', r));
+        // Se separan
+        for grupo in ["dl", "sl", "sr", "nr", "lr", "bs", "ns", "st", "cc", "nn", "mp", "pt"] {
+            let mut c = grupo.chars();
+            assert!(!es_grupo_inseparable(c.next().unwrap(), c.next().unwrap(), r), "{}", grupo);
+        }
+    }
+
+    #[test]
+    fn grupo_tl_segun_la_variante() {
+        assert!(!es_grupo_inseparable('t', 'l', &SILABEO_ESPANOL));
+        assert!(es_grupo_inseparable('t', 'l', &SILABEO_ESPANOL_AMERICA));
+        assert!(es_grupo_inseparable('T', 'L', &SILABEO_ESPANOL_AMERICA));
+        // El resto de grupos no cambia
+        assert!(es_grupo_inseparable('p', 'r', &SILABEO_ESPANOL_AMERICA));
+        assert!(!es_grupo_inseparable('d', 'l', &SILABEO_ESPANOL_AMERICA));
+
+        assert_eq!(espana("atleta"), "at-le-ta");
+        assert_eq!(america("atleta"), "a-tle-ta");
+        assert_eq!(espana("Atlántico"), "At-lán-ti-co");
+        assert_eq!(america("Atlántico"), "A-tlán-ti-co");
+        assert_eq!(espana("Mazatlán"), "Ma-zat-lán");
+        assert_eq!(america("Mazatlán"), "Ma-za-tlán");
+        assert_eq!(america("transatlántico"), "trans-a-tlán-ti-co");
+        // En ambas, sin otra consonante delante no hay nada que separar
+        assert_eq!(espana("Tlaxcala"), "Tlax-ca-la");
+        assert_eq!(america("Tlaxcala"), "Tlax-ca-la");
+    }
+
+    #[test]
+    fn diptongos() {
+        let r = &SILABEO_ESPANOL;
+        // Abierta + cerrada, cerrada + abierta, dos cerradas
+        for (v1, v2) in [('a', 'i'), ('e', 'u'), ('o', 'i'), ('i', 'a'), ('u', 'e'), ('i', 'o'),
+                         ('i', 'u'), ('u', 'i'), ('a', 'y'), ('e', 'y'), ('o', 'y'), ('u', 'y')] {
+            assert!(forman_diptongo(v1, v2, r), "{}{}", v1, v2);
+        }
+        // La tilde en la abierta no rompe el diptongo
+        assert!(forman_diptongo('á', 'i', r));
+        assert!(forman_diptongo('i', 'ó', r));
+        assert!(forman_diptongo('A', 'U', r));
+        // Diéresis: la ü suena y forma diptongo
+        assert!(forman_diptongo('ü', 'e', r));
+        assert!(forman_diptongo('ü', 'i', r));
+    }
+
+    #[test]
+    fn hiatos() {
+        let r = &SILABEO_ESPANOL;
+        // Dos abiertas
+        for (v1, v2) in [('a', 'e'), ('e', 'a'), ('o', 'e'), ('e', 'o'), ('a', 'o'), ('e', 'e'),
+                         ('o', 'o'), ('á', 'e'), ('e', 'ó'), ('A', 'E')] {
+            assert!(!forman_diptongo(v1, v2, r), "{}{}", v1, v2);
+        }
+        // Cerrada con tilde
+        for (v1, v2) in [('í', 'a'), ('a', 'í'), ('ú', 'a'), ('e', 'ú'), ('í', 'o'), ('o', 'í'), ('Í', 'A')] {
+            assert!(!forman_diptongo(v1, v2, r), "{}{}", v1, v2);
+        }
+    }
+
+    #[test]
+    fn u_muda() {
+        assert_eq!(espana("aquí"), "a-quí");
+        assert_eq!(espana("seguí"), "se-guí");
+        assert_eq!(espana("aquel"), "a-quel");
+        assert_eq!(espana("queso"), "que-so");
+        assert_eq!(espana("guitarra"), "gui-ta-rra");
+        assert_eq!(espana("guerrero"), "gue-rre-ro");
+        assert_eq!(espana("conquista"), "con-quis-ta");
+        assert_eq!(espana("exquisito"), "ex-qui-si-to");
+        assert_eq!(espana("distinguí"), "dis-tin-guí");
+        assert_eq!(espana("Quique"), "Qui-que");
+        assert_eq!(espana("AQUÍ"), "A-QUÍ");
+        // La u suena ante a y o, y con diéresis
+        assert_eq!(espana("agua"), "a-gua");
+        assert_eq!(espana("antiguo"), "an-ti-guo");
+        assert_eq!(espana("pingüino"), "pin-güi-no");
+        assert_eq!(espana("argüir"), "ar-güir");
+        assert_eq!(espana("averigüé"), "a-ve-ri-güé");
+        // Sin q ni g delante, la u es vocal
+        assert_eq!(espana("huí"), "hu-í");
+        assert_eq!(espana("actúe"), "ac-tú-e");
+    }
+
+    #[test]
+    fn y_vocal() {
+        assert_eq!(espana("rey"), "rey");
+        assert_eq!(espana("muy"), "muy");
+        assert_eq!(espana("hoy"), "hoy");
+        assert_eq!(espana("convoy"), "con-voy");
+        assert_eq!(espana("Paraguay"), "Pa-ra-guay");
+        assert_eq!(espana("buey"), "buey");
+        assert_eq!(espana("Aymara"), "Ay-ma-ra");
+        assert_eq!(espana("Reynaldo"), "Rey-nal-do");
+        // Ante vocal, o sin vocal delante, es consonante
+        assert_eq!(espana("reyes"), "re-yes");
+        assert_eq!(espana("ayer"), "a-yer");
+        assert_eq!(espana("hoyo"), "ho-yo");
+        assert_eq!(espana("yema"), "ye-ma");
+        assert_eq!(espana("y"), "y");
+    }
+
+    #[test]
+    fn suena_vocal_por_contexto() {
+        let r = &SILABEO_ESPANOL;
+        let vocales = |palabra: &str| -> String {
+            let chars: Vec<char> = palabra.chars().collect();
+            (0..chars.len()).map(|k| if suena_vocal(&chars, k, r) { 'v' } else { 'c' }).collect()
+        };
+        assert_eq!(vocales("que"), "ccv");
+        assert_eq!(vocales("gua"), "cvv");
+        assert_eq!(vocales("güe"), "cvv");
+        assert_eq!(vocales("rey"), "cvv");
+        assert_eq!(vocales("reyes"), "cvcvc");
+        assert_eq!(vocales("yo"), "cv");
+        // Otras lenguas no tienen estas reglas
+        let eu = crate::tabla::SILABEO_EUSKARA;
+        let chars: Vec<char> = "gue".chars().collect();
+        assert!(suena_vocal(&chars, 1, &eu));
+    }
+}
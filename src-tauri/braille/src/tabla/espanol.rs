@@ -0,0 +1,380 @@
+// ─── Tabla española (CBE/ONCE) ───────────────────────────────────────────────
+// Signografía básica de la Comisión Braille Española: alfabeto con vocales
+// acentuadas, ñ y ü, dígitos en la serie a–j tras el prefijo numérico, y los
+// signos de puntuación y símbolos de uso general, algunos de varias celdas.
+
+use super::{Indicador, TablaBraille};
+use crate::{puntos_a_byte, silabear_espanol, SILABEO_ESPANOL_AMERICA};
+
+/// Braille español de la ONCE.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TablaEspanol;
+
+impl TablaBraille for TablaEspanol {
+    fn nombre(&self) -> &str {
+        "es"
+    }
+
+    fn letra(&self, c: char) -> Option<Vec<u8>> {
+        letra_minuscula(c).map(|puntos| vec![puntos_a_byte(&puntos)])
+    }
+
+    fn digito(&self, c: char) -> Option<Vec<u8>> {
+        digito_a_puntos(c).map(|puntos| vec![puntos_a_byte(&puntos)])
+    }
+
+    fn puntuacion(&self, c: char) -> Option<Vec<u8>> {
+        puntuacion_a_puntos(c).map(|celdas| celdas.iter().map(|p| puntos_a_byte(p)).collect())
+    }
+
+    fn indicador(&self, indicador: Indicador) -> Vec<u8> {
+        let celdas: &[&[u8]] = match indicador {
+            Indicador::Mayuscula => &[PREFIJO_MAYUSCULA],
+            Indicador::MayusculasApertura => PASAJE_MAYUSCULAS,
+            Indicador::MayusculasCierre => &[PREFIJO_MAYUSCULA],
+            Indicador::Numero => &[PREFIJO_NUMERO],
+            Indicador::GuionDivision => &[GUION_DIVISION],
+            Indicador::LetraTrasNumero => &[INTERRUPTOR],
+            Indicador::Superindice => &[SUPERINDICE],
+            Indicador::Subindice => &[SUBINDICE],
+            Indicador::ResaltePalabra => RESALTE_PALABRA,
+            Indicador::ResalteApertura => RESALTE_APERTURA,
+            Indicador::ResalteCierre => RESALTE_CIERRE,
+        };
+        celdas.iter().map(|p| puntos_a_byte(p)).collect()
+    }
+}
+
+/// Braille español con el silabeo de México y el resto de América: los
+/// mismos signos, pero el grupo tl no se separa (a-tle-ta).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TablaEspanolAmerica;
+
+impl TablaBraille for TablaEspanolAmerica {
+    fn nombre(&self) -> &str {
+        "es-419"
+    }
+
+    fn letra(&self, c: char) -> Option<Vec<u8>> {
+        TablaEspanol.letra(c)
+    }
+
+    fn digito(&self, c: char) -> Option<Vec<u8>> {
+        TablaEspanol.digito(c)
+    }
+
+    fn puntuacion(&self, c: char) -> Option<Vec<u8>> {
+        TablaEspanol.puntuacion(c)
+    }
+
+    fn indicador(&self, indicador: Indicador) -> Vec<u8> {
+        TablaEspanol.indicador(indicador)
+    }
+
+    fn silabear(&self, palabra: &str) -> Vec<usize> {
+        silabear_espanol(palabra, &SILABEO_ESPANOL_AMERICA)
+    }
+}
+
+fn letra_minuscula(c: char) -> Option<Vec<u8>> {
+    match c {
+        'a' => Some(vec![1]),
+        'b' => Some(vec![1, 2]),
+        'c' => Some(vec![1, 4]),
+        'd' => Some(vec![1, 4, 5]),
+        'e' => Some(vec![1, 5]),
+        'f' => Some(vec![1, 2, 4]),
+        'g' => Some(vec![1, 2, 4, 5]),
+        'h' => Some(vec![1, 2, 5]),
+        'i' => Some(vec![2, 4]),
+        'j' => Some(vec![2, 4, 5]),
+        'k' => Some(vec![1, 3]),
+        'l' => Some(vec![1, 2, 3]),
+        'm' => Some(vec![1, 3, 4]),
+        'n' => Some(vec![1, 3, 4, 5]),
+        'o' => Some(vec![1, 3, 5]),
+        'p' => Some(vec![1, 2, 3, 4]),
+        'q' => Some(vec![1, 2, 3, 4, 5]),
+        'r' => Some(vec![1, 2, 3, 5]),
+        's' => Some(vec![2, 3, 4]),
+        't' => Some(vec![2, 3, 4, 5]),
+        'u' => Some(vec![1, 3, 6]),
+        'v' => Some(vec![1, 2, 3, 6]),
+        'w' => Some(vec![2, 4, 5, 6]),
+        'x' => Some(vec![1, 3, 4, 6]),
+        'y' => Some(vec![1, 3, 4, 5, 6]),
+        'z' => Some(vec![1, 3, 5, 6]),
+        '\u{00E1}' => Some(vec![1, 2, 3, 5, 6]), // á
+        '\u{00E9}' => Some(vec![2, 3, 4, 6]),     // é
+        '\u{00ED}' => Some(vec![3, 4]),            // í
+        '\u{00F3}' => Some(vec![3, 4, 6]),         // ó
+        '\u{00FA}' => Some(vec![2, 3, 4, 5, 6]),  // ú
+        '\u{00FC}' => Some(vec![1, 2, 5, 6]),      // ü
+        '\u{00F1}' => Some(vec![1, 2, 4, 5, 6]),  // ñ
+        _ => None,
+    }
+}
+
+fn digito_a_puntos(c: char) -> Option<Vec<u8>> {
+    match c {
+        '1' => Some(vec![1]),         // = a
+        '2' => Some(vec![1, 2]),      // = b
+        '3' => Some(vec![1, 4]),      // = c
+        '4' => Some(vec![1, 4, 5]),   // = d
+        '5' => Some(vec![1, 5]),      // = e
+        '6' => Some(vec![1, 2, 4]),   // = f
+        '7' => Some(vec![1, 2, 4, 5]),// = g
+        '8' => Some(vec![1, 2, 5]),   // = h
+        '9' => Some(vec![2, 4]),      // = i
+        '0' => Some(vec![2, 4, 5]),   // = j
+        _ => None,
+    }
+}
+
+/// Puntos de cada celda del signo. Interrogación, exclamación y comillas son
+/// iguales al abrir y al cerrar; paréntesis, corchetes y llaves, no.
+fn puntuacion_a_puntos(c: char) -> Option<&'static [&'static [u8]]> {
+    let celdas: &[&[u8]] = match c {
+        '.' => &[&[3]],
+        ',' => &[&[2]],
+        ';' => &[&[2, 3]],
+        ':' => &[&[2, 5]],
+        // Guion tipográfico normal (no de división braille):
+        '-' | '\u{2010}' | '\u{2011}' | '\u{2013}' => &[&[3, 6]],
+        '\u{2014}' => &[&[3, 6], &[3, 6]],                 // — raya
+        '\u{2026}' => &[&[3], &[3], &[3]],                  // … puntos suspensivos
+        '?' | '\u{00BF}' => &[&[2, 6]],
+        '!' | '\u{00A1}' => &[&[2, 3, 5]],
+        '"' | '\u{201C}' | '\u{201D}' | '\u{00AB}' | '\u{00BB}' => &[&[2, 3, 6]],
+        '\u{2018}' | '\u{2019}' => &[&[6], &[2, 3, 6]],      // ‘ ’ comillas simples
+        '\'' => &[&[3]],                                     // apóstrofo
+        '(' => &[&[1, 2, 6]],
+        ')' => &[&[3, 4, 5]],
+        '[' => &[&[1, 2, 3, 5, 6]],
+        ']' => &[&[2, 3, 4, 5, 6]],
+        '{' => &[&[5], &[1, 2, 3]],
+        '}' => &[&[4, 5, 6], &[2]],
+        '/' => &[&[6], &[2]],
+        '*' => &[&[3, 5], &[3, 5]],
+        '@' => &[&[5]],
+        '%' => &[&[4, 5, 6], &[3, 5, 6]],
+        '\u{2030}' => &[&[4, 5, 6], &[3, 5, 6], &[3, 5, 6]],  // ‰
+        '\u{20AC}' => &[&[4, 5, 6], &[1, 5]],               // €
+        '$' => &[&[4, 5, 6], &[1, 4, 5]],
+        '\u{00A3}' => &[&[4, 5, 6], &[1, 2, 3]],             // £
+        '\u{00A7}' => &[&[3, 4, 6]],                         // §
+        '\u{00B0}' => &[&[3, 5, 6]],                         // ° grado
+        '\u{2022}' => &[&[4, 5, 6], &[2, 5, 6]],             // • viñeta
+        // Operadores; el signo menos es el guion
+        '+' => &[&[2, 3, 5]],
+        '\u{2212}' => &[&[3, 6]],                            // − menos
+        '\u{00D7}' => &[&[2, 3, 6]],                         // × por
+        '\u{00F7}' => &[&[2, 5, 6]],                         // ÷ entre
+        '=' => &[&[2, 3, 5, 6]],
+        '<' => &[&[5], &[1, 3]],
+        '>' => &[&[4, 6], &[2]],
+        _ => return None,
+    };
+    Some(celdas)
+}
+
+const PREFIJO_MAYUSCULA: &[u8] = &[4, 6];
+const PREFIJO_NUMERO: &[u8] = &[3, 4, 5, 6];
+
+// Pasaje de más de tres palabras en mayúsculas: puntos 2,5 y 4,6 ante la
+// primera; la última lleva el prefijo de mayúscula, que cierra el pasaje.
+const PASAJE_MAYUSCULAS: &[&[u8]] = &[&[2, 5], &[4, 6]];
+
+// Guion de división braille (CBE/ONCE): puntos 3,6
+// Se inserta al final de la línea cuando se parte una palabra por sílabas.
+const GUION_DIVISION: &[u8] = &[3, 6];
+
+// Punto 5: separa el número de una letra a–j que lo sigue ("3a" ≠ "31").
+const INTERRUPTOR: &[u8] = &[5];
+
+// Signografía matemática: exponente (puntos 1,6) y subíndice (puntos 3,4).
+const SUPERINDICE: &[u8] = &[1, 6];
+const SUBINDICE: &[u8] = &[3, 4];
+
+// Resalte (cursiva, negrita, subrayado): puntos 4,5,6 y el punto 2 ante una
+// palabra; 4,5,6 y 2,3,5,6 abren un pasaje y 4,5,6 y 3 lo cierran.
+const RESALTE_PALABRA: &[&[u8]] = &[&[4, 5, 6], &[2]];
+const RESALTE_APERTURA: &[&[u8]] = &[&[4, 5, 6], &[2, 3, 5, 6]];
+const RESALTE_CIERRE: &[&[u8]] = &[&[4, 5, 6], &[3]];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signo(c: char) -> Vec<Vec<u8>> {
+        let celdas = TablaEspanol.puntuacion(c).unwrap_or_else(|| panic!("sin celda: {}", c));
+        celdas.iter()
+            .map(|&byte| (1..=6).filter(|p| byte & (1 << (p - 1)) != 0).collect())
+            .collect()
+    }
+
+    #[test]
+    fn puntuacion_basica() {
+        assert_eq!(signo('.'), [vec![3]]);
+        assert_eq!(signo(','), [vec![2]]);
+        assert_eq!(signo(';'), [vec![2, 3]]);
+        assert_eq!(signo(':'), [vec![2, 5]]);
+        assert_eq!(signo('¿'), signo('?'));
+        assert_eq!(signo('¡'), signo('!'));
+        assert_eq!(signo('-'), [vec![3, 6]]);
+        assert_eq!(signo('–'), [vec![3, 6]]);
+    }
+
+    #[test]
+    fn comillas_y_apostrofo() {
+        for c in ['"', '«', '»', '“', '”'] {
+            assert_eq!(signo(c), [vec![2, 3, 6]], "{}", c);
+        }
+        assert_eq!(signo('‘'), [vec![6], vec![2, 3, 6]]);
+        assert_eq!(signo('’'), [vec![6], vec![2, 3, 6]]);
+        assert_eq!(signo('\''), [vec![3]]);
+    }
+
+    #[test]
+    fn raya_y_puntos_suspensivos() {
+        assert_eq!(signo('—'), [vec![3, 6], vec![3, 6]]);
+        assert_eq!(signo('…'), [vec![3], vec![3], vec![3]]);
+    }
+
+    #[test]
+    fn parentesis_corchetes_y_llaves() {
+        assert_eq!(signo('('), [vec![1, 2, 6]]);
+        assert_eq!(signo(')'), [vec![3, 4, 5]]);
+        assert_eq!(signo('['), [vec![1, 2, 3, 5, 6]]);
+        assert_eq!(signo(']'), [vec![2, 3, 4, 5, 6]]);
+        assert_eq!(signo('{'), [vec![5], vec![1, 2, 3]]);
+        assert_eq!(signo('}'), [vec![4, 5, 6], vec![2]]);
+    }
+
+    #[test]
+    fn simbolos() {
+        assert_eq!(signo('/'), [vec![6], vec![2]]);
+        assert_eq!(signo('*'), [vec![3, 5], vec![3, 5]]);
+        assert_eq!(signo('@'), [vec![5]]);
+        assert_eq!(signo('%'), [vec![4, 5, 6], vec![3, 5, 6]]);
+        assert_eq!(signo('‰'), [vec![4, 5, 6], vec![3, 5, 6], vec![3, 5, 6]]);
+        assert_eq!(signo('§'), [vec![3, 4, 6]]);
+        assert_eq!(signo('°'), [vec![3, 5, 6]]);
+    }
+
+    #[test]
+    fn monedas() {
+        assert_eq!(signo('€'), [vec![4, 5, 6], vec![1, 5]]);
+        assert_eq!(signo('$'), [vec![4, 5, 6], vec![1, 4, 5]]);
+        assert_eq!(signo('£'), [vec![4, 5, 6], vec![1, 2, 3]]);
+    }
+
+    /// Celdas del texto como puntos, con las opciones dadas.
+    fn celdas_con(texto: &str, opciones: &crate::OpcionesCodificacion) -> Vec<Vec<u8>> {
+        let documento = crate::Documento::desde_texto(texto);
+        let (celdas, _) = crate::codificar_documento(&documento, &TablaEspanol, opciones);
+        celdas.iter()
+            .filter(|c| c.salto.is_none())
+            .map(|c| (1..=6).filter(|p| c.byte & (1 << (p - 1)) != 0).collect())
+            .collect()
+    }
+
+    fn celdas(texto: &str) -> Vec<Vec<u8>> {
+        celdas_con(texto, &crate::OpcionesCodificacion::default())
+    }
+
+    const NUM: &[u8] = &[3, 4, 5, 6];
+
+    #[test]
+    fn coma_decimal() {
+        // 3,14: la coma no interrumpe el número
+        assert_eq!(celdas("3,14"), [NUM, &[1, 4], &[2], &[1], &[1, 4, 5]]);
+        // coma seguida de espacio: termina el número
+        assert_eq!(celdas("3, 4"), [NUM, &[1, 4], &[2], &[], NUM, &[1, 4, 5]]);
+    }
+
+    #[test]
+    fn punto_entre_cifras() {
+        // Texto de la regla 9.2 (por defecto): el punto reinicia el número
+        assert_eq!(celdas("1.809"), [NUM, &[1], &[3], NUM, &[1, 2, 5], &[2, 4, 5], &[2, 4]]);
+        // Ejemplo del documento: punto de millares, 6 celdas
+        let millares = crate::OpcionesCodificacion {
+            punto_en_numero: crate::PuntoEnNumero::Millares,
+            ..crate::OpcionesCodificacion::default()
+        };
+        assert_eq!(celdas_con("1.809", &millares), [NUM, &[1], &[3], &[1, 2, 5], &[2, 4, 5], &[2, 4]]);
+        // Punto final tras un número: nada que continuar
+        assert_eq!(celdas_con("son 2.", &millares).last(), Some(&vec![3]));
+    }
+
+    #[test]
+    fn otros_signos_terminan_el_numero() {
+        assert_eq!(celdas("3-4"), [NUM, &[1, 4], &[3, 6], NUM, &[1, 4, 5]]);
+        assert_eq!(celdas("10:30"), [NUM, &[1], &[2, 4, 5], &[2, 5], NUM, &[1, 4], &[2, 4, 5]]);
+    }
+
+    #[test]
+    fn letra_tras_numero() {
+        // a–j se confundirían con cifras: punto 5 entre medias
+        assert_eq!(celdas("3a"), [NUM, &[1, 4], &[5], &[1]]);
+        assert_eq!(celdas("12h"), [NUM, &[1], &[1, 2], &[5], &[1, 2, 5]]);
+        // k–z no
+        assert_eq!(celdas("4x"), [NUM, &[1, 4, 5], &[1, 3, 4, 6]]);
+        // una mayúscula ya lleva su prefijo
+        assert_eq!(celdas("3A"), [NUM, &[1, 4], &[4, 6], &[1]]);
+        // tras un espacio no hace falta
+        assert_eq!(celdas("3 a"), [NUM, &[1, 4], &[], &[1]]);
+    }
+
+    const MAY: &[u8] = &[4, 6];
+
+    #[test]
+    fn pasaje_en_mayusculas() {
+        let (n, o, i, r) = (&[1, 3, 4, 5][..], &[1, 3, 5][..], &[2, 4][..], &[1, 2, 3, 5][..]);
+        // Hasta tres palabras, un prefijo por palabra
+        assert_eq!(celdas("NO IR NO"), [MAY, n, o, &[], MAY, i, r, &[], MAY, n, o]);
+        // Más de tres: apertura ante la primera, prefijo solo ante la última
+        assert_eq!(celdas("NO IR, NO IR"), [
+            &[2, 5], MAY, n, o, &[], i, r, &[2], &[], n, o, &[], MAY, i, r,
+        ]);
+        // Tras el pasaje, las mayúsculas vuelven a llevar su prefijo
+        let texto = celdas("NO IR NO IR. Ir NO");
+        assert_eq!(texto[texto.len() - 3..], [MAY, n, o]);
+    }
+
+    #[test]
+    fn pasaje_con_numeros() {
+        // El número no cuenta como palabra ni corta el pasaje
+        let pasaje = celdas("COVID19 EN 2020 NO IR");
+        assert_eq!(pasaje[..2], [&[2, 5], MAY]);
+        assert_eq!(pasaje.iter().filter(|c| c.as_slice() == MAY).count(), 2);
+        // Tres palabras y un número no son pasaje
+        assert_eq!(celdas("COVID19 EN 2020 NO")[0], MAY);
+        // Las cifras siguen a las letras con su prefijo
+        assert_eq!(celdas("MP3"), [MAY, &[1, 3, 4], &[1, 2, 3, 4], NUM, &[1, 4]]);
+    }
+
+    #[test]
+    fn palabras_con_mayusculas_intercaladas() {
+        // Un prefijo ante cada mayúscula; ninguna es palabra en mayúsculas
+        let mcdonald = celdas("McDonald");
+        assert_eq!(mcdonald[..4], [MAY, &[1, 3, 4], &[1, 4], MAY]);
+        assert_eq!(mcdonald.len(), 10);
+        let iphone = celdas("iPhone");
+        assert_eq!(iphone[..3], [&[2, 4], MAY, &[1, 2, 3, 4]]);
+        // y terminan un pasaje
+        let texto = celdas("NO QUIERO UN iPhone NUEVO");
+        assert_eq!(texto.iter().filter(|c| c.as_slice() == MAY).count(), 5);
+    }
+
+    #[test]
+    fn apostrofo_tipografico_entre_letras() {
+        let bytes = |texto: &str| -> Vec<u8> {
+            crate::codificar_texto(texto, &TablaEspanol).iter().map(|c| c.byte).collect()
+        };
+        // d’Artagnan: apóstrofo; ‘hola’: comillas simples
+        assert_eq!(bytes("d’a"), bytes("d'a"));
+        assert_eq!(bytes("‘a’"), [0b100000, 0b100110, 0b000001, 0b100000, 0b100110]);
+    }
+}
@@ -0,0 +1,219 @@
+// ─── Tablas integradas de otras lenguas peninsulares ─────────────────────────
+// Portugués, catalán, gallego y euskera. Los caracteres salen de las tablas
+// liblouis incluidas (pt.ctb, ca.ctb…); el silabeo, de las reglas de cada
+// lengua donde difieren de las del español.
+
+use super::{Indicador, TablaBraille, TablaLiblouis};
+use crate::{silabear_con, ReglasSilabeo};
+
+/// Portugués: ã y õ son vocales nasales que forman diptongo (mão, põe);
+/// lh y nh no se separan, rr y ss sí (car-ro, pas-so).
+pub const SILABEO_PORTUGUES: ReglasSilabeo = ReglasSilabeo {
+    vocales: "aeiouáéíóúâêôàãõü",
+    fuertes: "aeoáéóâêôà",
+    debiles_acentuadas: "íú",
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "vr", "ch", "lh", "nh",
+    ],
+    h_intercalada: false,
+    u_muda: true,
+    y_vocal: false,
+};
+
+/// Catalán: la ï rompe el diptongo (ve-ï-na); ny y ll no se separan, rr y
+/// ss sí (ter-ra, pas-sa). La l·l se parte por el punto volat.
+pub const SILABEO_CATALAN: ReglasSilabeo = ReglasSilabeo {
+    vocales: "aeiouàèéíïòóúü",
+    fuertes: "aeoàèéòó",
+    debiles_acentuadas: "íïú",
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "ny", "ll",
+    ],
+    h_intercalada: false,
+    u_muda: true,
+    y_vocal: false,
+};
+
+/// Gallego: como el español, más el dígrafo nh (u-nha).
+pub const SILABEO_GALEGO: ReglasSilabeo = ReglasSilabeo {
+    vocales: "aeiouáéíóúü",
+    fuertes: "aeoáéó",
+    debiles_acentuadas: "íú",
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "ch", "ll", "rr", "nh",
+    ],
+    h_intercalada: true,
+    u_muda: true,
+    y_vocal: false,
+};
+
+/// Euskera: las africadas tx, ts, tz y los dígrafos tt, dd son un solo
+/// sonido y no se separan (e-txe, be-tti).
+pub const SILABEO_EUSKARA: ReglasSilabeo = ReglasSilabeo {
+    vocales: "aeiouáéíóúü",
+    fuertes: "aeoáéó",
+    debiles_acentuadas: "íú",
+    inseparables: &[
+        "bl", "br", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "pl",
+        "pr", "tr", "ll", "rr", "tx", "ts", "tz", "tt", "dd",
+    ],
+    h_intercalada: false,
+    u_muda: false,
+    y_vocal: false,
+};
+
+/// Código de la tabla, archivo liblouis con sus caracteres y reglas de silabeo.
+const IDIOMAS: &[(&str, &str, &ReglasSilabeo)] = &[
+    ("pt", "pt.ctb", &SILABEO_PORTUGUES),
+    ("ca", "ca.ctb", &SILABEO_CATALAN),
+    ("gl", "gl.ctb", &SILABEO_GALEGO),
+    ("eu", "eu.ctb", &SILABEO_EUSKARA),
+];
+
+/// Tabla de una lengua con caracteres de liblouis y silabeo propio.
+#[derive(Clone, Debug)]
+pub struct TablaIdioma {
+    codigo: &'static str,
+    caracteres: TablaLiblouis,
+    silabeo: &'static ReglasSilabeo,
+}
+
+impl TablaIdioma {
+    pub fn nueva(codigo: &str) -> Option<TablaIdioma> {
+        let (codigo, archivo, silabeo) = IDIOMAS.iter().find(|(c, _, _)| *c == codigo)?;
+        let caracteres = TablaLiblouis::incluida(archivo)?.ok()?;
+        Some(TablaIdioma { codigo, caracteres, silabeo })
+    }
+}
+
+impl TablaBraille for TablaIdioma {
+    fn nombre(&self) -> &str {
+        self.codigo
+    }
+
+    fn letra(&self, c: char) -> Option<Vec<u8>> {
+        self.caracteres.letra(c)
+    }
+
+    fn digito(&self, c: char) -> Option<Vec<u8>> {
+        self.caracteres.digito(c)
+    }
+
+    fn puntuacion(&self, c: char) -> Option<Vec<u8>> {
+        self.caracteres.puntuacion(c)
+    }
+
+    fn indicador(&self, indicador: Indicador) -> Vec<u8> {
+        self.caracteres.indicador(indicador)
+    }
+
+    fn palabra(&self, palabra: &str) -> Option<Vec<u8>> {
+        self.caracteres.palabra(palabra)
+    }
+
+    fn secuencia(&self, texto: &[char]) -> Option<(usize, Vec<u8>)> {
+        self.caracteres.secuencia(texto)
+    }
+
+    fn silabear(&self, palabra: &str) -> Vec<usize> {
+        silabear_con(palabra, self.silabeo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puntos_a_byte;
+    use crate::tabla::{tabla_para_idioma, TABLAS_INCLUIDAS};
+
+    fn silabas(palabra: &str, reglas: &ReglasSilabeo) -> Vec<String> {
+        let cortes = silabear_con(palabra, reglas);
+        cortes.iter().enumerate()
+            .map(|(k, &inicio)| {
+                let fin = cortes.get(k + 1).copied().unwrap_or(palabra.len());
+                palabra[inicio..fin].to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn todas_las_tablas_cargan() {
+        for (codigo, _, _) in IDIOMAS {
+            assert!(TablaIdioma::nueva(codigo).is_some(), "{}", codigo);
+        }
+    }
+
+    #[test]
+    fn tabla_segun_dc_language() {
+        assert_eq!(tabla_para_idioma("pt-BR").nombre(), "pt");
+        assert_eq!(tabla_para_idioma("CA").nombre(), "ca");
+        assert_eq!(tabla_para_idioma("glg").nombre(), "gl");
+        assert_eq!(tabla_para_idioma("baq").nombre(), "eu");
+        assert_eq!(tabla_para_idioma("es-MX").nombre(), "es-419");
+        assert_eq!(tabla_para_idioma("es_ar").nombre(), "es-419");
+        assert_eq!(tabla_para_idioma("es-419").nombre(), "es-419");
+        assert_eq!(tabla_para_idioma("es-ES").nombre(), "es");
+        assert_eq!(tabla_para_idioma("spa").nombre(), "es");
+        assert_eq!(tabla_para_idioma("fr").nombre(), "es");
+        assert_eq!(tabla_para_idioma("").nombre(), "es");
+    }
+
+    #[test]
+    fn letras_propias() {
+        let pt = TablaIdioma::nueva("pt").unwrap();
+        assert_eq!(pt.letra('ç'), Some(vec![puntos_a_byte(&[1, 2, 3, 4, 6])]));
+        assert_eq!(pt.letra('ã'), Some(vec![puntos_a_byte(&[3, 4, 5])]));
+        assert_eq!(pt.letra('õ'), Some(vec![puntos_a_byte(&[2, 4, 6])]));
+        assert_eq!(pt.letra('â'), Some(vec![puntos_a_byte(&[1, 6])]));
+        assert_eq!(pt.letra('ê'), Some(vec![puntos_a_byte(&[1, 2, 6])]));
+        assert_eq!(pt.letra('ô'), Some(vec![puntos_a_byte(&[1, 4, 5, 6])]));
+
+        let ca = TablaIdioma::nueva("ca").unwrap();
+        assert_eq!(ca.letra('à'), Some(vec![puntos_a_byte(&[1, 2, 3, 5, 6])]));
+        assert_eq!(ca.letra('è'), Some(vec![puntos_a_byte(&[2, 3, 4, 6])]));
+        assert_eq!(ca.letra('ò'), Some(vec![puntos_a_byte(&[1, 2, 4, 6])]));
+        assert_eq!(ca.letra('ï'), Some(vec![puntos_a_byte(&[1, 2, 4, 5, 6])]));
+        assert_eq!(ca.puntuacion('·'), Some(vec![puntos_a_byte(&[3])]));
+
+        let gl = TablaIdioma::nueva("gl").unwrap();
+        assert_eq!(gl.letra('ñ'), Some(vec![puntos_a_byte(&[1, 2, 4, 5, 6])]));
+    }
+
+    #[test]
+    fn usa_las_reglas_always_y_word() {
+        let incluida = |nombre: &str| TABLAS_INCLUIDAS.iter()
+            .find(|(n, _)| *n == nombre)
+            .map(|(_, texto)| texto.to_string());
+        let caracteres = TablaLiblouis::desde_texto("prueba.ctb", "\
+            include pt.ctb\n\
+            always lh 1256-123\n\
+            word que 12345\n", incluida).unwrap();
+        let tabla = TablaIdioma { codigo: "pt", caracteres, silabeo: &SILABEO_PORTUGUES };
+        assert_eq!(tabla.secuencia(&['l', 'h', 'o']), Some((2, vec![0b110011, 0b000111])));
+        assert_eq!(tabla.palabra("que"), Some(vec![0b011111]));
+
+        let bytes: Vec<u8> = crate::codificar_texto("filho que", &tabla)
+            .iter().map(|c| c.byte).collect();
+        assert_eq!(bytes, vec![
+            0b001011, 0b001010, 0b110011, 0b000111, 0b010101, // f i lh o
+            0,
+            0b011111,                                         // que
+        ]);
+    }
+
+    #[test]
+    fn silabeo_propio() {
+        assert_eq!(silabas("filho", &SILABEO_PORTUGUES), ["fi", "lho"]);
+        assert_eq!(silabas("carro", &SILABEO_PORTUGUES), ["car", "ro"]);
+        assert_eq!(silabas("coração", &SILABEO_PORTUGUES), ["co", "ra", "ção"]);
+        assert_eq!(silabas("terra", &SILABEO_CATALAN), ["ter", "ra"]);
+        assert_eq!(silabas("canya", &SILABEO_CATALAN), ["ca", "nya"]);
+        assert_eq!(silabas("veïna", &SILABEO_CATALAN), ["ve", "ï", "na"]);
+        assert_eq!(silabas("unha", &SILABEO_GALEGO), ["u", "nha"]);
+        assert_eq!(silabas("etxea", &SILABEO_EUSKARA), ["e", "txe", "a"]);
+        assert_eq!(silabas("entzun", &SILABEO_EUSKARA), ["en", "tzun"]);
+    }
+}
@@ -0,0 +1,503 @@
+// ─── Tablas en formato liblouis (.ctb / .utb / .uti) ─────────────────────────
+// Subconjunto práctico de la sintaxis de liblouis, suficiente para las tablas
+// de grado 1 de muchas lenguas:
+//   include        otra tabla, relativa a la que la incluye
+//   letter, lowercase, uplow   letras (de uplow se toma la minúscula)
+//   digit          dígitos, que el codificador precede del signo de número
+//   punctuation, sign, math, hyphen   signos
+//   capsletter, numsign        indicadores de mayúscula y de número
+//   begcapsphrase, endcapsphrase   pasaje en mayúsculas (el cierre, siempre
+//                  ante la última palabra: "endcapsphrase before")
+//   letsign        separador entre un número y la letra que lo sigue
+//   begemphword, begemphphrase, endemphphrase   indicadores de resalte (de
+//                  cualquier clase: el codificador no distingue cursiva de negrita)
+//   always         secuencia de caracteres → celdas (una sola letra también)
+//   word           palabra completa → celdas
+// Los demás códigos se ignoran; las reglas `nofor` se saltan y el prefijo
+// `noback` no cambia nada (solo traducimos hacia braille).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::{Indicador, TablaBraille};
+use crate::{puntos_a_byte, ErrorBraille};
+
+/// Tablas liblouis incluidas en el binario, por nombre de archivo.
+pub const TABLAS_INCLUIDAS: &[(&str, &str)] = &[
+    ("es-cbe.ctb", include_str!("../../tablas/es-cbe.ctb")),
+    ("letras-latinas.uti", include_str!("../../tablas/letras-latinas.uti")),
+    ("digitos.uti", include_str!("../../tablas/digitos.uti")),
+    ("puntuacion.uti", include_str!("../../tablas/puntuacion.uti")),
+    ("pt.ctb", include_str!("../../tablas/pt.ctb")),
+    ("ca.ctb", include_str!("../../tablas/ca.ctb")),
+    ("gl.ctb", include_str!("../../tablas/gl.ctb")),
+    ("eu.ctb", include_str!("../../tablas/eu.ctb")),
+];
+
+/// Límite de anidamiento de `include`, contra tablas que se incluyen entre sí.
+const MAX_INCLUDES: usize = 16;
+
+/// Guion de división si la tabla no define `hyphen` ni el signo `-`.
+const GUION_POR_DEFECTO: &[u8] = &[3, 6];
+
+/// Separador entre número y letra si la tabla no define `letsign`.
+const INTERRUPTOR_POR_DEFECTO: &[u8] = &[5];
+
+/// Indicadores de exponente y subíndice; liblouis no tiene opcode para ellos
+/// y se usan los de la CBE.
+const SUPERINDICE_POR_DEFECTO: &[u8] = &[1, 6];
+const SUBINDICE_POR_DEFECTO: &[u8] = &[3, 4];
+
+/// Apertura de pasaje en mayúsculas si la tabla no la define: la de la CBE.
+/// El cierre, por defecto, es el prefijo de mayúscula.
+const PASAJE_MAYUSCULAS_POR_DEFECTO: &[&[u8]] = &[&[2, 5], &[4, 6]];
+
+/// Indicadores de resalte si la tabla no los define: los de la CBE.
+const RESALTE_PALABRA_POR_DEFECTO: &[&[u8]] = &[&[4, 5, 6], &[2]];
+const RESALTE_APERTURA_POR_DEFECTO: &[&[u8]] = &[&[4, 5, 6], &[2, 3, 5, 6]];
+const RESALTE_CIERRE_POR_DEFECTO: &[&[u8]] = &[&[4, 5, 6], &[3]];
+
+#[derive(Clone, Debug, Default)]
+pub struct TablaLiblouis {
+    nombre: String,
+    letras: HashMap<char, Vec<u8>>,
+    digitos: HashMap<char, Vec<u8>>,
+    signos: HashMap<char, Vec<u8>>,
+    /// Entradas `always` de más de un carácter, de la más larga a la más corta.
+    secuencias: Vec<(Vec<char>, Vec<u8>)>,
+    palabras: HashMap<String, Vec<u8>>,
+    mayuscula: Vec<u8>,
+    numero: Vec<u8>,
+    guion: Option<Vec<u8>>,
+    letra_tras_numero: Option<Vec<u8>>,
+    pasaje_mayusculas: Option<Vec<u8>>,
+    cierre_mayusculas: Option<Vec<u8>>,
+    resalte_palabra: Option<Vec<u8>>,
+    resalte_apertura: Option<Vec<u8>>,
+    resalte_cierre: Option<Vec<u8>>,
+}
+
+impl TablaLiblouis {
+    /// Tabla incluida en el binario (ver `TABLAS_INCLUIDAS`).
+    pub fn incluida(nombre: &str) -> Option<Result<TablaLiblouis, ErrorBraille>> {
+        let texto = buscar_incluida(nombre)?;
+        Some(TablaLiblouis::desde_texto(nombre, texto, |inc| {
+            buscar_incluida(inc).map(str::to_string)
+        }))
+    }
+
+    /// Lee una tabla del disco. Los `include` se buscan junto a ella y, si no
+    /// están, entre las tablas incluidas.
+    pub fn desde_archivo(ruta: &Path) -> Result<TablaLiblouis, ErrorBraille> {
+        let nombre = ruta.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let texto = fs::read_to_string(ruta).map_err(|e| ErrorBraille::Apertura {
+            ruta: ruta.display().to_string(),
+            detalle: e.to_string(),
+        })?;
+        let dir = ruta.parent().unwrap_or(Path::new("")).to_path_buf();
+        TablaLiblouis::desde_texto(&nombre, &texto, |inc| {
+            fs::read_to_string(dir.join(inc)).ok()
+                .or_else(|| buscar_incluida(inc).map(str::to_string))
+        })
+    }
+
+    /// Interpreta el texto de una tabla. `leer_include` devuelve el contenido
+    /// de cada tabla incluida a partir de su nombre.
+    pub fn desde_texto(
+        nombre: &str,
+        texto: &str,
+        leer_include: impl Fn(&str) -> Option<String>,
+    ) -> Result<TablaLiblouis, ErrorBraille> {
+        let mut tabla = TablaLiblouis { nombre: nombre.to_string(), ..Default::default() };
+        tabla.cargar(nombre, texto, &leer_include, 0)?;
+        tabla.secuencias.sort_by_key(|(secuencia, _)| std::cmp::Reverse(secuencia.len()));
+        Ok(tabla)
+    }
+
+    fn cargar(
+        &mut self,
+        archivo: &str,
+        texto: &str,
+        leer_include: &impl Fn(&str) -> Option<String>,
+        profundidad: usize,
+    ) -> Result<(), ErrorBraille> {
+        for (n, linea) in texto.lines().enumerate() {
+            let error = |detalle: String| ErrorBraille::TablaInvalida {
+                tabla: archivo.to_string(),
+                linea: n + 1,
+                detalle,
+            };
+
+            let mut campos = linea.split_whitespace();
+            let mut opcode = match campos.next() {
+                None => continue,
+                Some(c) if c.starts_with('#') => continue,
+                Some(c) => c,
+            };
+            match opcode {
+                "nofor" => continue,
+                "noback" => match campos.next() {
+                    Some(c) => opcode = c,
+                    None => return Err(error("falta el código tras noback".to_string())),
+                },
+                _ => {}
+            }
+            let mut operando = || {
+                campos.next().ok_or_else(|| error(format!("faltan operandos de {}", opcode)))
+            };
+
+            match opcode {
+                "include" => {
+                    let inc = operando()?;
+                    if profundidad >= MAX_INCLUDES {
+                        return Err(error(format!("demasiados include anidados ({})", inc)));
+                    }
+                    let contenido = leer_include(inc)
+                        .ok_or_else(|| error(format!("no se encontró la tabla incluida {}", inc)))?;
+                    self.cargar(inc, &contenido, leer_include, profundidad + 1)?;
+                }
+                "letter" | "lowercase" | "uplow" | "digit" | "punctuation" | "sign" | "math"
+                | "hyphen" | "always" | "word" => {
+                    let caracteres = desescapar(operando()?).map_err(&error)?;
+                    let mut puntos = operando()?;
+                    if opcode == "uplow" {
+                        // "uplow Aa 17,1": puntos de la mayúscula, coma, de la minúscula
+                        puntos = puntos.rsplit(',').next().unwrap_or(puntos);
+                    }
+                    let celdas = leer_puntos(puntos).map_err(&error)?;
+                    self.definir(opcode, &caracteres, celdas).map_err(&error)?;
+                }
+                "capsletter" => self.mayuscula = leer_puntos(operando()?).map_err(&error)?,
+                "numsign" => self.numero = leer_puntos(operando()?).map_err(&error)?,
+                "letsign" => {
+                    self.letra_tras_numero = Some(leer_puntos(operando()?).map_err(&error)?);
+                }
+                // "begemphword italic 456-2", "endemphphrase italic after 456-3":
+                // los puntos son el último operando
+                "begemphword" | "begemphphrase" | "endemphphrase" | "begcapsphrase"
+                | "endcapsphrase" => {
+                    let puntos = campos.last()
+                        .ok_or_else(|| error(format!("faltan operandos de {}", opcode)))?;
+                    let celdas = Some(leer_puntos(puntos).map_err(&error)?);
+                    match opcode {
+                        "begemphword" => self.resalte_palabra = celdas,
+                        "begemphphrase" => self.resalte_apertura = celdas,
+                        "endemphphrase" => self.resalte_cierre = celdas,
+                        "begcapsphrase" => self.pasaje_mayusculas = celdas,
+                        _ => self.cierre_mayusculas = celdas,
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn definir(&mut self, opcode: &str, caracteres: &[char], celdas: Vec<u8>) -> Result<(), String> {
+        let unico = match caracteres {
+            [c] => Some(*c),
+            _ => None,
+        };
+        match (opcode, unico) {
+            ("word", _) => {
+                let palabra: String = caracteres.iter().flat_map(|c| c.to_lowercase()).collect();
+                self.palabras.insert(palabra, celdas);
+            }
+            ("always", None) => {
+                let secuencia = caracteres.iter().flat_map(|c| c.to_lowercase()).collect();
+                self.secuencias.push((secuencia, celdas));
+            }
+            ("uplow", _) => {
+                let minuscula = *caracteres.last().ok_or("uplow sin caracteres")?;
+                self.letras.insert(minuscula, celdas);
+            }
+            (_, None) => return Err(format!("{} admite un solo carácter", opcode)),
+            ("hyphen", Some(c)) => {
+                self.guion = Some(celdas.clone());
+                self.signos.insert(c, celdas);
+            }
+            ("digit", Some(c)) => {
+                self.digitos.insert(c, celdas);
+            }
+            (_, Some(c)) if c.is_alphabetic() => {
+                self.letras.insert(c.to_lowercase().next().unwrap_or(c), celdas);
+            }
+            (_, Some(c)) if c.is_ascii_digit() && opcode == "always" => {
+                self.digitos.insert(c, celdas);
+            }
+            (_, Some(c)) => {
+                self.signos.insert(c, celdas);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TablaBraille for TablaLiblouis {
+    fn nombre(&self) -> &str {
+        &self.nombre
+    }
+
+    fn letra(&self, c: char) -> Option<Vec<u8>> {
+        self.letras.get(&c).cloned()
+    }
+
+    fn digito(&self, c: char) -> Option<Vec<u8>> {
+        self.digitos.get(&c).cloned()
+    }
+
+    fn puntuacion(&self, c: char) -> Option<Vec<u8>> {
+        self.signos.get(&c).cloned()
+    }
+
+    fn indicador(&self, indicador: Indicador) -> Vec<u8> {
+        match indicador {
+            Indicador::Mayuscula => self.mayuscula.clone(),
+            Indicador::MayusculasApertura => self.pasaje_mayusculas.clone()
+                .unwrap_or_else(|| celdas_por_defecto(PASAJE_MAYUSCULAS_POR_DEFECTO)),
+            Indicador::MayusculasCierre => self.cierre_mayusculas.clone()
+                .unwrap_or_else(|| self.mayuscula.clone()),
+            Indicador::Numero => self.numero.clone(),
+            Indicador::GuionDivision => self.guion.clone()
+                .or_else(|| self.signos.get(&'-').cloned())
+                .unwrap_or_else(|| vec![puntos_a_byte(GUION_POR_DEFECTO)]),
+            Indicador::LetraTrasNumero => self.letra_tras_numero.clone()
+                .unwrap_or_else(|| vec![puntos_a_byte(INTERRUPTOR_POR_DEFECTO)]),
+            Indicador::Superindice => vec![puntos_a_byte(SUPERINDICE_POR_DEFECTO)],
+            Indicador::Subindice => vec![puntos_a_byte(SUBINDICE_POR_DEFECTO)],
+            Indicador::ResaltePalabra => self.resalte_palabra.clone()
+                .unwrap_or_else(|| celdas_por_defecto(RESALTE_PALABRA_POR_DEFECTO)),
+            Indicador::ResalteApertura => self.resalte_apertura.clone()
+                .unwrap_or_else(|| celdas_por_defecto(RESALTE_APERTURA_POR_DEFECTO)),
+            Indicador::ResalteCierre => self.resalte_cierre.clone()
+                .unwrap_or_else(|| celdas_por_defecto(RESALTE_CIERRE_POR_DEFECTO)),
+        }
+    }
+
+    fn palabra(&self, palabra: &str) -> Option<Vec<u8>> {
+        self.palabras.get(palabra).cloned()
+    }
+
+    fn secuencia(&self, texto: &[char]) -> Option<(usize, Vec<u8>)> {
+        self.secuencias.iter()
+            .find(|(secuencia, _)| {
+                texto.len() >= secuencia.len()
+                    && texto.iter().zip(secuencia).all(|(&c, &s)| a_minuscula(c) == s)
+            })
+            .map(|(secuencia, celdas)| (secuencia.len(), celdas.clone()))
+    }
+}
+
+fn buscar_incluida(nombre: &str) -> Option<&'static str> {
+    TABLAS_INCLUIDAS.iter()
+        .find(|(n, _)| *n == nombre)
+        .map(|(_, texto)| *texto)
+}
+
+fn a_minuscula(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Celdas de una lista de puntos estilo liblouis: "1-36-0" son tres celdas,
+/// la última en blanco.
+fn leer_puntos(puntos: &str) -> Result<Vec<u8>, String> {
+    puntos.split('-')
+        .map(|celda| {
+            if celda == "0" {
+                return Ok(0);
+            }
+            if celda.is_empty() {
+                return Err(format!("celda vacía en «{}»", puntos));
+            }
+            let mut activos = Vec::new();
+            for p in celda.chars() {
+                match p.to_digit(10) {
+                    Some(d @ 1..=6) => activos.push(d as u8),
+                    _ => return Err(format!("punto no válido «{}» en «{}» (solo 1–6)", p, puntos)),
+                }
+            }
+            Ok(puntos_a_byte(&activos))
+        })
+        .collect()
+}
+
+/// Operando de caracteres con los escapes de liblouis: `\s` espacio, `\t`
+/// tabulador, `\\` barra, `\xhhhh` y `\yhhhhh` código Unicode.
+fn desescapar(operando: &str) -> Result<Vec<char>, String> {
+    let mut resultado = Vec::new();
+    let mut chars = operando.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            resultado.push(c);
+            continue;
+        }
+        let escapado = match chars.next() {
+            Some('s') => ' ',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            Some(tipo @ ('x' | 'y')) => {
+                let digitos = if tipo == 'x' { 4 } else { 5 };
+                let hex: String = chars.by_ref().take(digitos).collect();
+                u32::from_str_radix(&hex, 16).ok()
+                    .filter(|_| hex.len() == digitos)
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("escape no válido \\{}{}", tipo, hex))?
+            }
+            Some(otro) => return Err(format!("escape no admitido \\{}", otro)),
+            None => return Err("barra invertida al final del operando".to_string()),
+        };
+        resultado.push(escapado);
+    }
+    Ok(resultado)
+}
+
+fn celdas_por_defecto(puntos: &[&[u8]]) -> Vec<u8> {
+    puntos.iter().map(|p| puntos_a_byte(p)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tabla::TablaEspanol;
+
+    fn sin_includes(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn es_cbe_coincide_con_la_tabla_integrada() {
+        let liblouis = TablaLiblouis::incluida("es-cbe.ctb").unwrap().unwrap();
+        let integrada = TablaEspanol;
+
+        for c in "abcdefghijklmnopqrstuvwxyzáéíóúüñ".chars() {
+            assert_eq!(liblouis.letra(c), integrada.letra(c), "letra {}", c);
+        }
+        for c in "0123456789".chars() {
+            assert_eq!(liblouis.digito(c), integrada.digito(c), "dígito {}", c);
+        }
+        for c in ".,;:-?¿!¡\"“”«»‘’'‐‑–—…()[]{}/*@%‰€$£§°•+−×÷=<>".chars() {
+            assert_eq!(liblouis.puntuacion(c), integrada.puntuacion(c), "signo {}", c);
+        }
+        for indicador in [
+            Indicador::Mayuscula,
+            Indicador::MayusculasApertura,
+            Indicador::MayusculasCierre,
+            Indicador::Numero,
+            Indicador::GuionDivision,
+            Indicador::LetraTrasNumero,
+            Indicador::Superindice,
+            Indicador::Subindice,
+            Indicador::ResaltePalabra,
+            Indicador::ResalteApertura,
+            Indicador::ResalteCierre,
+        ] {
+            assert_eq!(liblouis.indicador(indicador), integrada.indicador(indicador));
+        }
+    }
+
+    #[test]
+    fn es_cbe_codifica_igual_que_la_tabla_integrada() {
+        let liblouis = TablaLiblouis::incluida("es-cbe.ctb").unwrap().unwrap();
+        let texto = "¿Cuántos? ¡1.809 ovejas, ONCE y Ñandú! NO QUIERO IR AHORA.";
+        let bytes = |tabla: &dyn TablaBraille| -> Vec<u8> {
+            crate::codificar_texto(texto, tabla).iter().map(|c| c.byte).collect()
+        };
+        assert_eq!(bytes(&liblouis), bytes(&TablaEspanol));
+    }
+
+    #[test]
+    fn tablas_incluidas_se_cargan() {
+        for (nombre, _) in TABLAS_INCLUIDAS {
+            assert!(TablaLiblouis::incluida(nombre).unwrap().is_ok(), "{}", nombre);
+        }
+    }
+
+    #[test]
+    fn puntos_de_varias_celdas() {
+        assert_eq!(leer_puntos("1-36-0"), Ok(vec![0b000001, 0b100100, 0]));
+        assert!(leer_puntos("17").is_err());
+        assert!(leer_puntos("1--2").is_err());
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(desescapar(r"\x00F1"), Ok(vec!['ñ']));
+        assert_eq!(desescapar(r"a\sb\\"), Ok(vec!['a', ' ', 'b', '\\']));
+        assert_eq!(desescapar(r"\y1D11E"), Ok(vec!['𝄞']));
+        assert!(desescapar(r"\x12").is_err());
+        assert!(desescapar(r"\q").is_err());
+    }
+
+    #[test]
+    fn always_y_word() {
+        let tabla = TablaLiblouis::desde_texto("prueba.ctb", "\
+            # comentario\n\
+            always ch 16 resto de la línea ignorado\n\
+            always ... 3-3-3\n\
+            always ß 2346\n\
+            word para 1234-1\n\
+            nofor always x 1\n\
+            noback sign % 456-356\n\
+            unknownopcode foo 1\n", sin_includes).unwrap();
+
+        assert_eq!(tabla.secuencia(&['C', 'h', 'a']), Some((2, vec![0b100001])));
+        assert_eq!(tabla.secuencia(&['.', '.', '.', 'x']), Some((3, vec![4, 4, 4])));
+        assert_eq!(tabla.secuencia(&['c', 'a']), None);
+        assert_eq!(tabla.letra('ß'), Some(vec![0b101110]));
+        assert_eq!(tabla.letra('x'), None);
+        assert_eq!(tabla.palabra("para"), Some(vec![0b001111, 0b000001]));
+        assert_eq!(tabla.puntuacion('%'), Some(vec![0b111000, 0b110100]));
+    }
+
+    #[test]
+    fn el_codificador_usa_always_y_word() {
+        let tabla = TablaLiblouis::desde_texto("prueba.ctb", "\
+            include es-cbe.ctb\n\
+            always ch 16\n\
+            word para 1234-1\n", |inc| buscar_incluida(inc).map(str::to_string)).unwrap();
+        let bytes: Vec<u8> = crate::codificar_texto("Chile PARA", &tabla)
+            .iter().map(|c| c.byte).collect();
+        let m = puntos_a_byte(&[4, 6]);
+        assert_eq!(bytes, vec![
+            m, 0b100001, 0b001010, 0b000111, 0b010001, // ⠨ ch i l e
+            0,
+            m, 0b001111, 0b000001,                     // ⠨ para
+        ]);
+    }
+
+    #[test]
+    fn uplow_toma_la_minuscula() {
+        // Los puntos de la mayúscula (aquí con el punto 7) no se usan: la
+        // mayúscula se escribe con el indicador
+        let tabla = TablaLiblouis::desde_texto("t", "uplow Aa 17,1\nuplow Bb 12", sin_includes).unwrap();
+        assert_eq!(tabla.letra('a'), Some(vec![1]));
+        assert_eq!(tabla.letra('b'), Some(vec![3]));
+        assert_eq!(tabla.letra('A'), None);
+    }
+
+    #[test]
+    fn errores_con_linea() {
+        let error = TablaLiblouis::desde_texto("mala.ctb", "letter a 1\nletter b 9\n", sin_includes)
+            .unwrap_err();
+        assert!(matches!(error, ErrorBraille::TablaInvalida { linea: 2, .. }), "{:?}", error);
+
+        let error = TablaLiblouis::desde_texto("mala.ctb", "include falta.uti\n", sin_includes)
+            .unwrap_err();
+        assert!(error.to_string().contains("falta.uti"));
+
+        let error = TablaLiblouis::desde_texto("mala.ctb", "punctuation ab 1\n", sin_includes)
+            .unwrap_err();
+        assert!(matches!(error, ErrorBraille::TablaInvalida { linea: 1, .. }));
+    }
+
+    #[test]
+    fn include_circular() {
+        let error = TablaLiblouis::desde_texto("a.ctb", "include a.ctb", |_| {
+            Some("include a.ctb".to_string())
+        }).unwrap_err();
+        assert!(error.to_string().contains("anidados"));
+    }
+}
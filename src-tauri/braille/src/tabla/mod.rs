@@ -0,0 +1,129 @@
+// ─── Tablas braille ──────────────────────────────────────────────────────────
+// El codificador no sabe de ningún idioma: pregunta a la tabla qué celdas
+// corresponden a cada letra, dígito o signo y cuáles son los indicadores
+// (mayúscula, número, guion de división). Cada conversión elige su tabla.
+
+mod espanol;
+mod idiomas;
+mod liblouis;
+
+use std::path::Path;
+
+use crate::ErrorBraille;
+
+pub use espanol::{TablaEspanol, TablaEspanolAmerica};
+pub use idiomas::{
+    TablaIdioma, SILABEO_CATALAN, SILABEO_EUSKARA, SILABEO_GALEGO, SILABEO_PORTUGUES,
+};
+pub use liblouis::{TablaLiblouis, TABLAS_INCLUIDAS};
+
+/// Indicadores que el codificador y el formateador insertan por su cuenta.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indicador {
+    /// Antes de una letra mayúscula (o de una palabra entera en mayúsculas).
+    Mayuscula,
+    /// Antes de la primera palabra de un pasaje en mayúsculas.
+    MayusculasApertura,
+    /// Antes de la última palabra de un pasaje en mayúsculas.
+    MayusculasCierre,
+    /// Antes de una serie de dígitos.
+    Numero,
+    /// Al final de la línea cuando una palabra se parte por sílabas.
+    GuionDivision,
+    /// Entre un número y la letra minúscula que lo sigue, si esa letra se
+    /// confundiría con una cifra ("3a", "12h").
+    LetraTrasNumero,
+    /// Antes de un exponente ("x²").
+    Superindice,
+    /// Antes de un subíndice ("H₂O").
+    Subindice,
+    /// Antes de una palabra resaltada (cursiva, negrita, subrayado).
+    ResaltePalabra,
+    /// Antes de la primera palabra de un pasaje resaltado.
+    ResalteApertura,
+    /// Tras la última palabra de un pasaje resaltado.
+    ResalteCierre,
+}
+
+/// Correspondencia entre caracteres y celdas de un sistema braille. Todas las
+/// celdas se devuelven ya como bytes (bit 0 = punto 1); una entrada puede
+/// ocupar varias celdas.
+pub trait TablaBraille: Send + Sync {
+    /// Identificador corto con el que se elige la tabla ("es").
+    fn nombre(&self) -> &str;
+    /// Celdas de una letra, que llega siempre en minúscula.
+    fn letra(&self, c: char) -> Option<Vec<u8>>;
+    /// Celdas de un dígito, sin el indicador numérico.
+    fn digito(&self, c: char) -> Option<Vec<u8>>;
+    /// Celdas de un signo de puntuación.
+    fn puntuacion(&self, c: char) -> Option<Vec<u8>>;
+    fn indicador(&self, indicador: Indicador) -> Vec<u8>;
+    /// Celdas de una palabra entera (en minúsculas) con entrada propia.
+    fn palabra(&self, _palabra: &str) -> Option<Vec<u8>> {
+        None
+    }
+    /// Secuencia de varios caracteres al comienzo de `texto` con entrada
+    /// propia: cuántos caracteres abarca y sus celdas.
+    fn secuencia(&self, _texto: &[char]) -> Option<(usize, Vec<u8>)> {
+        None
+    }
+    /// Posiciones (en bytes) donde empieza cada sílaba de la palabra.
+    fn silabear(&self, palabra: &str) -> Vec<usize> {
+        crate::silabear(palabra)
+    }
+}
+
+/// Nombres de las tablas integradas, en el orden en que se ofrecen.
+pub const TABLAS: &[&str] = &["es", "es-419", "pt", "ca", "gl", "eu"];
+
+/// Extensiones de los archivos de tabla de liblouis.
+const EXTENSIONES_LIBLOUIS: &[&str] = &["ctb", "utb", "uti", "tbl", "dis"];
+
+/// Tabla integrada con ese nombre.
+pub fn tabla_por_nombre(nombre: &str) -> Option<Box<dyn TablaBraille>> {
+    match nombre {
+        "es" => Some(Box::new(TablaEspanol)),
+        "es-419" => Some(Box::new(TablaEspanolAmerica)),
+        _ => TablaIdioma::nueva(nombre).map(|t| Box::new(t) as Box<dyn TablaBraille>),
+    }
+}
+
+/// Tabla integrada para el idioma de un libro (`dc:language`: "pt-BR",
+/// "cat", "eu"…). Si el idioma no tiene tabla propia, la española; el
+/// español de una región que no es España ("es-MX", "es-419") lleva el
+/// silabeo americano.
+pub fn tabla_para_idioma(idioma: &str) -> Box<dyn TablaBraille> {
+    let mut partes = idioma.split(['-', '_']);
+    let principal = partes.next().unwrap_or("").trim().to_ascii_lowercase();
+    let region = partes.next().unwrap_or("").trim().to_ascii_lowercase();
+    if matches!(principal.as_str(), "es" | "spa") && !region.is_empty() && region != "es" {
+        return Box::new(TablaEspanolAmerica);
+    }
+    let codigo = match principal.as_str() {
+        "por" => "pt",
+        "cat" => "ca",
+        "glg" => "gl",
+        "eus" | "baq" => "eu",
+        otro => otro,
+    };
+    tabla_por_nombre(codigo).unwrap_or_else(|| Box::new(TablaEspanol))
+}
+
+/// Tabla pedida por nombre. Además de las integradas, acepta el nombre de
+/// una tabla liblouis incluida ("es-cbe.ctb") o la ruta de un archivo de
+/// tabla liblouis.
+pub fn elegir_tabla(nombre: &str) -> Result<Box<dyn TablaBraille>, ErrorBraille> {
+    if let Some(tabla) = tabla_por_nombre(nombre) {
+        return Ok(tabla);
+    }
+    if let Some(tabla) = TablaLiblouis::incluida(nombre) {
+        return Ok(Box::new(tabla?));
+    }
+    let ruta = Path::new(nombre);
+    let es_liblouis = ruta.extension()
+        .is_some_and(|ext| EXTENSIONES_LIBLOUIS.iter().any(|e| ext.eq_ignore_ascii_case(e)));
+    if es_liblouis {
+        return Ok(Box::new(TablaLiblouis::desde_archivo(ruta)?));
+    }
+    Err(ErrorBraille::TablaDesconocida(nombre.to_string()))
+}
@@ -1,18 +1,49 @@
-mod braille;
+use braille::{Conversion, ErrorBraille, FormatoSalida, Maquetacion, OpcionesCodificacion};
 
 #[tauri::command]
-fn convertir(path: String) -> Result<String, String> {
-    braille::convertir_epub_a_bin(&path)
+fn convertir(
+    path: String,
+    formato: Option<FormatoSalida>,
+    maquetacion: Option<Maquetacion>,
+    tabla: Option<String>,
+    codificacion: Option<OpcionesCodificacion>,
+) -> Result<Conversion, ErrorBraille> {
+    let tabla = tabla.as_deref().map(braille::elegir_tabla).transpose()?;
+    braille::convertir_epub(&path, formato.unwrap_or_default(), &maquetacion.unwrap_or_default(), &codificacion.unwrap_or_default(), tabla.as_deref())
 }
 
 #[tauri::command]
-fn extraer_texto(path: String) -> Result<String, String> {
+fn extraer_texto(path: String) -> Result<String, ErrorBraille> {
     braille::extraer_texto_epub(&path)
 }
 
 #[tauri::command]
-fn convertir_texto(texto: String, bin_path: String) -> Result<String, String> {
-    braille::convertir_texto_a_bin(&texto, &bin_path)
+fn convertir_texto(
+    texto: String,
+    bin_path: String,
+    formato: Option<FormatoSalida>,
+    maquetacion: Option<Maquetacion>,
+    tabla: Option<String>,
+    codificacion: Option<OpcionesCodificacion>,
+) -> Result<Conversion, ErrorBraille> {
+    let tabla = braille::elegir_tabla(tabla.as_deref().unwrap_or("es"))?;
+    braille::convertir_texto(&texto, &bin_path, formato.unwrap_or_default(), &maquetacion.unwrap_or_default(), &codificacion.unwrap_or_default(), tabla.as_ref())
+}
+
+#[tauri::command]
+fn previsualizar(
+    texto: String,
+    maquetacion: Option<Maquetacion>,
+    tabla: Option<String>,
+    codificacion: Option<OpcionesCodificacion>,
+) -> Result<Vec<Vec<String>>, ErrorBraille> {
+    let tabla = braille::elegir_tabla(tabla.as_deref().unwrap_or("es"))?;
+    braille::previsualizar_texto(&texto, &maquetacion.unwrap_or_default(), &codificacion.unwrap_or_default(), tabla.as_ref())
+}
+
+#[tauri::command]
+fn maquetacion_por_defecto() -> Maquetacion {
+    Maquetacion::default()
 }
 
 #[tauri::command]
@@ -26,7 +57,7 @@ fn obtener_tamano(path: String) -> Result<u64, String> {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![convertir, extraer_texto, convertir_texto, obtener_tamano])
+        .invoke_handler(tauri::generate_handler![convertir, extraer_texto, convertir_texto, previsualizar, maquetacion_por_defecto, obtener_tamano])
         .run(tauri::generate_context!())
         .expect("error al iniciar Tauri");
-}
\ No newline at end of file
+}
@@ -0,0 +1,13 @@
+// Espejo de `braille::ErrorBraille`, tal como lo serializa Rust.
+export type ErrorBraille = {
+  tipo: string;
+  mensaje: string;
+};
+
+// Texto para mostrar a partir de lo que rechace un `invoke`.
+export function mensajeError(e: unknown): string {
+  if (e && typeof e === "object" && "mensaje" in e) {
+    return String((e as ErrorBraille).mensaje);
+  }
+  return String(e);
+}
@@ -0,0 +1,82 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type NumeracionPagina = "superior" | "inferior" | "ninguna";
+
+// Espejo de `braille::Maquetacion` (los valores por defecto viven en Rust).
+export type Maquetacion = {
+  celdasPorLinea: number;
+  lineasPorPagina: number;
+  numeracion: NumeracionPagina;
+  sangria: number;
+  lineaEntreParrafos: boolean;
+  nivelCapitulo: number;
+  centrarTitulos: boolean;
+  lineasAntesTitulo: number;
+  lineasDespuesTitulo: number;
+  capituloEnPaginaNueva: boolean;
+};
+
+export type Sustitucion = "omitir" | "marcador" | "transliterar";
+
+// Espejo de `braille::Normalizacion` (sin ella, se aplican los valores por defecto de Rust).
+export type Normalizacion = {
+  componer: boolean;
+  ligaduras: boolean;
+  espacios: boolean;
+  guionBlando: "quitar" | "puntoDeCorte";
+  comillasRectas: boolean;
+};
+
+// Espejo de `braille::Enfasis`: resaltes del libro que llevan indicador.
+export type Enfasis = { cursiva: boolean; negrita: boolean; subrayado: boolean };
+
+// Espejo de `braille::Excepciones`: palabras con sus sílabas ("su-bli-me") y
+// prefijos con el comienzo que debe seguirles ("sub-r").
+export type Excepciones = { palabras: string[]; prefijos: string[] };
+
+// Espejo de `braille::Division`: qué cortes de sílaba sirven a final de línea.
+export type Division = { fragmentoMinimo: number; hiatos: boolean; nombresPropios: boolean };
+
+// Espejo de `braille::OpcionesCodificacion`.
+export type OpcionesCodificacion = {
+  normalizacion?: Normalizacion;
+  desconocidos: Sustitucion;
+  // "1.809": repetir el prefijo tras el punto (regla CBE 9.2) o punto de millares
+  puntoEnNumero?: "repitePrefijo" | "millares";
+  enfasis?: Enfasis;
+  excepciones?: Excepciones;
+  division?: Division;
+};
+
+// Espejo de `braille::Informe`.
+export type Posicion = { capitulo: number; bloque: number; columna: number };
+export type CaracterSinCelda = {
+  caracter: string;
+  codigo: string;
+  cantidad: number;
+  posiciones: Posicion[];
+};
+export type Informe = { sinCelda: CaracterSinCelda[] };
+
+// Espejo de `braille::Conversion`.
+export type Conversion = {
+  ruta: string;
+  lineas: number;
+  paginas: number;
+  tabla: string;
+  informe: Informe;
+};
+
+// Tablas integradas (`braille::tabla::TABLAS`) con su nombre para la interfaz.
+export const TABLAS: { valor: string; nombre: string }[] = [
+  { valor: "es", nombre: "Español (España)" },
+  { valor: "es-419", nombre: "Español (América)" },
+  { valor: "pt", nombre: "Português" },
+  { valor: "ca", nombre: "Català" },
+  { valor: "gl", nombre: "Galego" },
+  { valor: "eu", nombre: "Euskara" },
+];
+
+export function maquetacionPorDefecto(): Promise<Maquetacion> {
+  return invoke<Maquetacion>("maquetacion_por_defecto");
+}